@@ -1,3 +1,5 @@
+use crate::installer::context::AssumeUtxoProgress;
+use liana_ui::color::{InvalidThemeColor, Theme, ThemePalette};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use tracing_subscriber::filter;
@@ -15,6 +17,56 @@ pub struct Config {
     /// Start internal bitcoind executable.
     #[serde(default)]
     pub start_internal_bitcoind: bool,
+    /// Which palette to render with. Defaults to the built-in dark palette when unset.
+    pub theme: Option<ThemeConfig>,
+    /// Carried over from `installer::Context::assumeutxo_progress` when the install flow used
+    /// an assumeutxo snapshot, so the running app (not just the installer) can keep gating
+    /// spending on it across restarts, until background validation finishes. See
+    /// [`Config::assumeutxo_blocks_spending`].
+    #[serde(default)]
+    pub assumeutxo_progress: Option<AssumeUtxoProgress>,
+}
+
+/// Selects which palette `Config::theme` resolves to.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeConfig {
+    Dark,
+    Light,
+    /// A user-supplied TOML palette file of hex colors, one per [`Theme`] role.
+    Custom { path: PathBuf },
+}
+
+/// On-disk shape of a [`ThemeConfig::Custom`] palette file.
+#[derive(Debug, Clone, Deserialize)]
+struct ThemePaletteFile {
+    background: String,
+    background_secondary: String,
+    background_tertiary: String,
+    primary_button: String,
+    secondary_button: String,
+    success: String,
+    error: String,
+    warning: String,
+    text: String,
+    text_secondary: String,
+}
+
+impl From<ThemePaletteFile> for ThemePalette {
+    fn from(file: ThemePaletteFile) -> Self {
+        Self {
+            background: file.background,
+            background_secondary: file.background_secondary,
+            background_tertiary: file.background_tertiary,
+            primary_button: file.primary_button,
+            secondary_button: file.secondary_button,
+            success: file.success,
+            error: file.error,
+            warning: file.warning,
+            text: file.text,
+            text_secondary: file.text_secondary,
+        }
+    }
 }
 
 pub const DEFAULT_FILE_NAME: &str = "gui.toml";
@@ -27,6 +79,8 @@ impl Config {
             log_level: None,
             debug: None,
             start_internal_bitcoind,
+            theme: None,
+            assumeutxo_progress: None,
         }
     }
 
@@ -44,9 +98,41 @@ impl Config {
 
         // check if log_level field is valid
         config.log_level()?;
+        // check the theme resolves, be it a preset or a custom palette file
+        config.theme()?;
         Ok(config)
     }
 
+    /// Resolve `theme` into concrete colors, defaulting to the built-in dark palette when unset
+    /// and validating a custom palette file's hex entries.
+    pub fn theme(&self) -> Result<Theme, ConfigError> {
+        match &self.theme {
+            None | Some(ThemeConfig::Dark) => Ok(Theme::dark()),
+            Some(ThemeConfig::Light) => Ok(Theme::light()),
+            Some(ThemeConfig::Custom { path }) => {
+                let content = std::fs::read_to_string(path).map_err(|e| {
+                    ConfigError::InvalidField("theme", format!("Reading palette file: {}", e))
+                })?;
+                let palette: ThemePaletteFile = toml::from_str(&content).map_err(|e| {
+                    ConfigError::InvalidField("theme", format!("Parsing palette file: {}", e))
+                })?;
+                Theme::from_palette(&palette.into())
+                    .map_err(|e: InvalidThemeColor| ConfigError::InvalidField("theme", e.to_string()))
+            }
+        }
+    }
+
+    /// Whether spending should stay blocked (or clearly warned about) because `assumeutxo_progress`
+    /// records a snapshot that the internal bitcoind hasn't finished background-validating yet.
+    /// Delegates to [`AssumeUtxoProgress::is_fully_validated`] — the same check
+    /// `installer::Context::assumeutxo_blocks_spending` runs during install — so the gate
+    /// survives the handoff from the installer into the running app and across restarts.
+    pub fn assumeutxo_blocks_spending(&self) -> bool {
+        self.assumeutxo_progress
+            .as_ref()
+            .is_some_and(|progress| !progress.is_fully_validated())
+    }
+
     /// TODO: Deserialize directly in the struct.
     pub fn log_level(&self) -> Result<filter::LevelFilter, ConfigError> {
         if let Some(level) = &self.log_level {
@@ -116,3 +202,37 @@ pub fn default_datadir() -> Result<PathBuf, Box<dyn std::error::Error>> {
 
     Err("Failed to get default data directory".into())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with_progress(progress: Option<AssumeUtxoProgress>) -> Config {
+        let mut config = Config::new(PathBuf::from("lianad.toml"), false);
+        config.assumeutxo_progress = progress;
+        config
+    }
+
+    #[test]
+    fn spending_is_not_blocked_when_no_snapshot_is_in_progress() {
+        assert!(!config_with_progress(None).assumeutxo_blocks_spending());
+    }
+
+    #[test]
+    fn spending_is_blocked_while_validation_is_behind_the_snapshot() {
+        let config = config_with_progress(Some(AssumeUtxoProgress {
+            snapshot_height: 800_000,
+            validated_height: 400_000,
+        }));
+        assert!(config.assumeutxo_blocks_spending());
+    }
+
+    #[test]
+    fn spending_is_unblocked_once_validation_catches_up() {
+        let config = config_with_progress(Some(AssumeUtxoProgress {
+            snapshot_height: 800_000,
+            validated_height: 800_000,
+        }));
+        assert!(!config.assumeutxo_blocks_spending());
+    }
+}
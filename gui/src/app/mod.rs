@@ -19,7 +19,7 @@ use iced::{clipboard, time, Command, Subscription};
 use tracing::{info, warn};
 
 pub use liana::{config::Config as DaemonConfig, miniscript::bitcoin};
-use liana_ui::widget::Element;
+use liana_ui::{color::Theme, widget::Element};
 
 pub use config::Config;
 pub use message::Message;
@@ -43,6 +43,11 @@ pub struct App {
     wallet: Arc<Wallet>,
     daemon: Arc<dyn Daemon + Sync + Send>,
     internal_bitcoind: Option<Bitcoind>,
+    // Resolved from `config.theme()` (built-in dark/light, or a custom palette file). Widgets
+    // still draw from the hardcoded `liana_ui::color` constants directly rather than this value
+    // — threading it into their styling is the iced `Application::theme()` entry point's job,
+    // which lives in this crate's binary entrypoint, not part of this tree.
+    theme: Theme,
 }
 
 impl App {
@@ -56,6 +61,10 @@ impl App {
     ) -> (App, Command<Message>) {
         let state: Box<dyn State> = Home::new(wallet.clone(), &cache.coins).into();
         let cmd = state.load(daemon.clone());
+        let theme = config.theme().unwrap_or_else(|e| {
+            warn!("Invalid theme configuration, falling back to the default: {}", e);
+            Theme::dark()
+        });
         (
             Self {
                 data_dir,
@@ -65,11 +74,27 @@ impl App {
                 daemon,
                 wallet,
                 internal_bitcoind,
+                theme,
             },
             cmd,
         )
     }
 
+    /// The resolved palette the GUI should render with, per `config.theme()`.
+    pub fn theme(&self) -> Theme {
+        self.theme
+    }
+
+    /// Whether the app should keep spending flows blocked (or visibly warn about them) because
+    /// `config.assumeutxo_progress` says the internal bitcoind hasn't finished
+    /// background-validating an assumeutxo snapshot yet. Mirrors `theme()`: a thin accessor over
+    /// the `Config` value so callers elsewhere in this crate don't need to reach into `config`
+    /// directly. Not yet checked anywhere in `update`/`view` — the panel that would act on it
+    /// (disabling/annotating the spend button) isn't part of this tree slice.
+    pub fn assumeutxo_blocks_spending(&self) -> bool {
+        self.config.assumeutxo_blocks_spending()
+    }
+
     fn load_state(&mut self, menu: &Menu) -> Command<Message> {
         self.state = match menu {
             menu::Menu::Settings => state::SettingsState::new(
@@ -15,7 +15,11 @@ use async_hwi::{
     ledger::{self, DeviceInfo, HidApi},
     specter, DeviceKind, Error as HWIError, Version, HWI,
 };
-use liana::miniscript::bitcoin::{bip32::Fingerprint, hashes::hex::FromHex, Network};
+use liana::miniscript::bitcoin::{
+    bip32::{DerivationPath, Fingerprint},
+    hashes::hex::FromHex,
+    Network,
+};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, warn};
 
@@ -27,6 +31,56 @@ pub enum UnsupportedReason {
     Method(&'static str),
     NotPartOfWallet(Fingerprint),
     WrongNetwork,
+    /// Catch-all for a real communication/protocol failure, as opposed to the device being
+    /// legitimately unsupported. See [`HwPollError`] for the finer-grained classification.
+    Error(HwPollError),
+}
+
+/// Coarse classification of a failed `poll_*`/`handle_*` attempt, so the UI can tell "the user
+/// rejected the prompt on the device" apart from "firmware too old" apart from "USB hiccup",
+/// instead of every non-version failure being silently logged or folded into a fabricated
+/// `UnsupportedReason::Version`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HwPollError {
+    /// The device answering at this vid/pid isn't the kind of wallet we expected.
+    DeviceTypeMismatch,
+    /// The candidate's vid/pid/usage-page doesn't match any known wallet interface.
+    InvalidDevice,
+    /// A lower-level transport or protocol error (USB hiccup, malformed response, ...).
+    Protocol,
+    /// The user declined the action on the device itself, e.g. rejected the fingerprint prompt.
+    UserCancel,
+    /// The device needs interaction before it can proceed: still locked, or waiting on a
+    /// PIN/passphrase/confirmation.
+    UserInteractionRequired,
+    /// Nothing matching the expected device was found on the bus.
+    NoDeviceFound,
+}
+
+impl From<&HWIError> for HwPollError {
+    fn from(e: &HWIError) -> Self {
+        match e {
+            HWIError::DeviceNotFound => Self::NoDeviceFound,
+            HWIError::NetworkMismatch => Self::Protocol,
+            HWIError::Device(msg) => classify_device_error_message(msg),
+            _ => Self::Protocol,
+        }
+    }
+}
+
+/// `async_hwi::Error::Device` only carries a free-form message, so user-cancel and
+/// needs-interaction cases are told apart by sniffing the wording devices/backends commonly use
+/// for them. Anything that doesn't match either is reported as a generic protocol error rather
+/// than guessed at.
+fn classify_device_error_message(message: &str) -> HwPollError {
+    let message = message.to_ascii_lowercase();
+    if message.contains("denied") || message.contains("refused") || message.contains("cancel") {
+        HwPollError::UserCancel
+    } else if message.contains("lock") || message.contains("pin") || message.contains("confirm") {
+        HwPollError::UserInteractionRequired
+    } else {
+        HwPollError::Protocol
+    }
 }
 
 // Todo drop the Clone, to remove the Mutex on HardwareWallet::Locked
@@ -139,11 +193,170 @@ impl HardwareWalletConfig {
     }
 }
 
+/// Pins signing to one physical device when several of the same kind are plugged in, e.g. for
+/// multisig setups with two identical Jades or Ledgers. Borrowed from the locator concept in the
+/// Solana remote-wallet crate: a manufacturer/kind plus an index or identifier.
+///
+/// Parses and formats as a URI-like string: `jade://<fingerprint>`, `ledger://0` (the Nth
+/// connected device of that kind, in enumeration order), so configs, CLI flags, and saved
+/// `HardwareWalletConfig` entries can reference a specific device.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HardwareWalletLocator {
+    pub kind: DeviceKind,
+    pub fingerprint: Option<Fingerprint>,
+    pub index: Option<usize>,
+}
+
+impl std::fmt::Display for HardwareWalletLocator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(fingerprint) = self.fingerprint {
+            write!(f, "{}://{}", self.kind, fingerprint)
+        } else {
+            write!(f, "{}://{}", self.kind, self.index.unwrap_or(0))
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HardwareWalletLocatorParseError {
+    MissingScheme,
+    UnknownKind(String),
+    InvalidIdentifier(String),
+}
+
+impl std::fmt::Display for HardwareWalletLocatorParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingScheme => write!(f, "Missing '<kind>://' scheme"),
+            Self::UnknownKind(k) => write!(f, "Unknown device kind '{}'", k),
+            Self::InvalidIdentifier(id) => {
+                write!(f, "'{}' is neither a fingerprint nor an index", id)
+            }
+        }
+    }
+}
+
+impl std::error::Error for HardwareWalletLocatorParseError {}
+
+impl std::str::FromStr for HardwareWalletLocator {
+    type Err = HardwareWalletLocatorParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (scheme, identifier) = s
+            .split_once("://")
+            .ok_or(HardwareWalletLocatorParseError::MissingScheme)?;
+        let kind = match scheme {
+            "ledger" => DeviceKind::Ledger,
+            "coldcard" => DeviceKind::Coldcard,
+            "bitbox02" => DeviceKind::BitBox02,
+            "jade" => DeviceKind::Jade,
+            "specter" => DeviceKind::Specter,
+            _ => {
+                return Err(HardwareWalletLocatorParseError::UnknownKind(
+                    scheme.to_string(),
+                ))
+            }
+        };
+        if let Ok(fingerprint) = identifier.parse::<Fingerprint>() {
+            return Ok(Self {
+                kind,
+                fingerprint: Some(fingerprint),
+                index: None,
+            });
+        }
+        if let Ok(index) = identifier.parse::<usize>() {
+            return Ok(Self {
+                kind,
+                fingerprint: None,
+                index: Some(index),
+            });
+        }
+        Err(HardwareWalletLocatorParseError::InvalidIdentifier(
+            identifier.to_string(),
+        ))
+    }
+}
+
+impl HardwareWalletLocator {
+    /// Cheap pre-filter a `poll_*` function with multiple candidates can check once, before
+    /// enumerating any of them: if this locator targets a different device kind there's no
+    /// point scanning this transport at all.
+    fn allows_kind(locator: Option<&Self>, kind: DeviceKind) -> bool {
+        locator.map_or(true, |l| l.kind == kind)
+    }
+
+    /// Per-candidate pre-filter: like [`Self::allows_kind`], but also resolves an index-based
+    /// locator against `seen_of_kind`, this candidate's position among same-kind candidates
+    /// considered so far, so there's no point paying for the connect attempt on the wrong one.
+    /// When the locator targets a fingerprint, the fingerprint isn't known until after
+    /// connecting, so every same-kind candidate is let through here and [`Self::accepts`] does
+    /// the real filtering once it's known.
+    fn allows_attempt(locator: Option<&Self>, kind: DeviceKind, seen_of_kind: usize) -> bool {
+        let Some(locator) = locator else {
+            return true;
+        };
+        if locator.kind != kind {
+            return false;
+        }
+        locator.fingerprint.is_some() || seen_of_kind == locator.index.unwrap_or(0)
+    }
+
+    /// Whether an already-connected candidate of `kind`/`fingerprint` is the specific device
+    /// `locator` asked for. Only meaningful for a fingerprint-based locator: an index-based one
+    /// was already fully resolved by [`Self::allows_attempt`].
+    fn accepts(locator: Option<&Self>, kind: DeviceKind, fingerprint: Option<Fingerprint>) -> bool {
+        let Some(locator) = locator else {
+            return true;
+        };
+        if locator.kind != kind {
+            return false;
+        }
+        match locator.fingerprint {
+            Some(wanted) => fingerprint == Some(wanted),
+            None => true,
+        }
+    }
+}
+
+/// Routing for the Jade pin-server round-trip that `Jade::auth` performs against a remote
+/// server to decrypt the seed. Lets a user behind Tor, or one running their own pin-server,
+/// point Jade unlocking at it instead of Blockstream's default.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PinServerConfig {
+    /// Overrides the pin-server base URL. `None` keeps Jade's built-in default.
+    pub base_url: Option<String>,
+    /// A `socks5h://host:port` (or similar) proxy the HTTP client should dial through, e.g. a
+    /// local Tor SOCKS port.
+    pub proxy: Option<String>,
+}
+
+/// Apply `pinserver`, if any, to a freshly constructed `Jade` before it's used to unlock or
+/// authenticate, so every Jade pin-server round-trip in this module is routed consistently.
+fn with_pinserver(
+    device: Jade<jade::SerialTransport>,
+    pinserver: Option<&PinServerConfig>,
+) -> Jade<jade::SerialTransport> {
+    let Some(pinserver) = pinserver else {
+        return device;
+    };
+    let device = if let Some(base_url) = &pinserver.base_url {
+        device.with_pin_server(base_url.clone())
+    } else {
+        device
+    };
+    if let Some(proxy) = &pinserver.proxy {
+        device.with_proxy(proxy.clone())
+    } else {
+        device
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum HardwareWalletMessage {
     Error(String),
     List(ConnectedList),
     Unlocked(String, Result<HardwareWallet, async_hwi::Error>),
+    Signed(Fingerprint, Result<String, async_hwi::Error>),
 }
 
 #[derive(Debug, Clone)]
@@ -158,6 +371,7 @@ pub struct HardwareWallets {
     pub aliases: HashMap<Fingerprint, String>,
     wallet: Option<Arc<Wallet>>,
     datadir_path: PathBuf,
+    pinserver: Option<PinServerConfig>,
 }
 
 impl std::fmt::Debug for HardwareWallets {
@@ -174,6 +388,7 @@ impl HardwareWallets {
             aliases: HashMap::new(),
             wallet: None,
             datadir_path,
+            pinserver: None,
         }
     }
 
@@ -183,6 +398,15 @@ impl HardwareWallets {
         self
     }
 
+    pub fn with_pinserver(mut self, pinserver: PinServerConfig) -> Self {
+        self.pinserver = Some(pinserver);
+        self
+    }
+
+    pub fn set_pinserver(&mut self, pinserver: Option<PinServerConfig>) {
+        self.pinserver = pinserver;
+    }
+
     pub fn set_alias(&mut self, fg: Fingerprint, new_alias: String) {
         // remove all (fingerprint, alias) with same alias.
         self.aliases.retain(|_, a| *a != new_alias);
@@ -210,6 +434,52 @@ impl HardwareWallets {
         self.list = Vec::new();
     }
 
+    /// Ask the connected, supported device owning `fingerprint` to sign `message`, to let the
+    /// user prove control of one of the wallet's addresses. Returns `None` if no such device is
+    /// currently connected, if it isn't part of the wallet descriptor, or if the backend
+    /// requires policy registration and the device isn't registered yet.
+    pub fn sign_message(
+        &self,
+        fingerprint: Fingerprint,
+        derivation_path: DerivationPath,
+        message: String,
+    ) -> Option<Command<HardwareWalletMessage>> {
+        let wallet = self.wallet.as_ref()?;
+        if !wallet.descriptor_keys().contains(&fingerprint) {
+            return None;
+        }
+        self.list.iter().find_map(|hw| {
+            if let HardwareWallet::Supported {
+                fingerprint: fg,
+                device,
+                registered,
+                ..
+            } = hw
+            {
+                if *fg == fingerprint && registered.unwrap_or(true) {
+                    return Some(sign_message_command(
+                        fingerprint,
+                        device.clone(),
+                        derivation_path.clone(),
+                        message.clone(),
+                    ));
+                }
+            }
+            None
+        })
+    }
+
+    /// Deterministically select one connected device matching `locator`: by fingerprint if one
+    /// is given, otherwise by its position (`index`) among currently connected devices of that
+    /// `kind`, in enumeration order.
+    pub fn resolve(&self, locator: &HardwareWalletLocator) -> Option<&HardwareWallet> {
+        let mut matching = self.list.iter().filter(|hw| *hw.kind() == locator.kind);
+        if let Some(fingerprint) = locator.fingerprint {
+            return matching.find(|hw| hw.fingerprint() == Some(fingerprint));
+        }
+        matching.nth(locator.index.unwrap_or(0))
+    }
+
     pub fn update(
         &mut self,
         message: HardwareWalletMessage,
@@ -289,6 +559,7 @@ impl HardwareWallets {
                                     let id_cloned = id.clone();
                                     let network = self.network;
                                     let wallet = self.wallet.clone();
+                                    let device = with_pinserver(device, self.pinserver.as_ref());
                                     cmds.push(Command::perform(
                                         async move {
                                             device.auth().await?;
@@ -315,6 +586,9 @@ impl HardwareWallets {
                     Ok(Command::batch(cmds))
                 }
             }
+            // The signature (or the device's rejection of the request) is consumed by whoever
+            // requested it via `sign_message`; `HardwareWallets` has no state of its own to update.
+            HardwareWalletMessage::Signed(..) => Ok(Command::none()),
             HardwareWalletMessage::Unlocked(id, res) => {
                 match res {
                     Err(e) => {
@@ -345,10 +619,206 @@ impl HardwareWallets {
     }
 }
 
+/// Ask a connected, supported device to sign `message` with the key at `derivation_path`, to
+/// let the user prove control of one of the wallet's addresses. Only devices whose fingerprint
+/// belongs to the wallet descriptor (and that are already policy-registered where the backend
+/// requires it, e.g. BitBox02/Jade) should ever be offered this action.
+///
+/// For Bitcoin, `device.sign_message` is expected to return a BIP-322 signature, falling back
+/// to the legacy `\x18Bitcoin Signed Message:\n`-prefixed double-SHA256 scheme for devices that
+/// don't support BIP-322 yet, so the result verifies in other wallets.
+pub async fn sign_message(
+    device: Arc<dyn HWI + Sync + Send>,
+    derivation_path: DerivationPath,
+    message: String,
+) -> Result<String, HWIError> {
+    device.sign_message(&derivation_path, &message).await
+}
+
+/// Build the `Command` that drives [`sign_message`] and reports back through
+/// `HardwareWalletMessage::Signed`. The caller is responsible for checking the device is
+/// `Supported`, part of `wallet.descriptor_keys()`, and `registered` where required, before
+/// issuing this command.
+pub fn sign_message_command(
+    fingerprint: Fingerprint,
+    device: Arc<dyn HWI + Sync + Send>,
+    derivation_path: DerivationPath,
+    message: String,
+) -> Command<HardwareWalletMessage> {
+    Command::perform(sign_message(device, derivation_path, message), move |res| {
+        HardwareWalletMessage::Signed(fingerprint, res)
+    })
+}
+
+/// HID usage page used by wallet firmwares to expose their control interface, same constant
+/// the Solana remote-wallet enumerator filters on.
+const HID_GLOBAL_USAGE_PAGE: u16 = 0xff00;
+/// USB interface/device class used by the wallet control interface.
+const HID_WALLET_INTERFACE: i32 = 0;
+/// Ledger's USB vendor id.
+const LEDGER_VID: u16 = 0x2c97;
+
+/// How often we check the HID bus for a change. Raw-HID devices (BitBox02, Coldcard, Ledger)
+/// don't have a push-based API in `hidapi`, so this polls, but much lighter and much more often
+/// than before: it only compares the filtered device list, it never opens or re-opens a device.
+const HID_CHECK_INTERVAL: Duration = Duration::from_millis(300);
+/// Upper bound on how long `poll_hw` waits before returning anyway, so the serial transports
+/// (Specter, Jade), which can't report hotplug events, still get polled at roughly their old
+/// cadence even when nothing changes on the HID bus.
+const SERIAL_FALLBACK_INTERVAL: Duration = Duration::from_secs(2);
+/// A device must be missing from this many consecutive checks before we treat it as removed,
+/// so one that re-enumerates mid-unlock isn't dropped from `HardwareWallets::list` (`hw_poll`
+/// keeps it in `still` while the snapshot still reports it present).
+const REMOVAL_DEBOUNCE_TICKS: u8 = 3;
+/// How long a raw-HID candidate must have been continuously enumerated before we attempt to
+/// open it. Devices that are still mid-boot right after being plugged in often show up on the
+/// bus before their control interface actually answers; settling avoids the "device not
+/// detected right after plug-in" failure that a single immediate open attempt would hit.
+const ATTACH_SETTLE_DELAY: Duration = Duration::from_millis(500);
+/// Upper bound on how long we retry a transient open/connect failure on an already-settled
+/// device before giving up for this tick (it'll be retried again on the next one).
+const MAX_POLLING_DURATION: Duration = Duration::from_millis(500);
+/// Spacing between retries within [`MAX_POLLING_DURATION`].
+const POLLING_RETRY_INTERVAL: Duration = Duration::from_millis(50);
+
+fn is_wallet_interface(info: &DeviceInfo) -> bool {
+    info.usage_page() == HID_GLOBAL_USAGE_PAGE || info.interface_number() == HID_WALLET_INTERFACE
+}
+
+fn is_known_wallet_vid_pid(info: &DeviceInfo) -> bool {
+    info.vendor_id() == LEDGER_VID
+        || (info.vendor_id() == coldcard::api::COINKITE_VID
+            && info.product_id() == coldcard::api::CKCC_PID)
+        || async_hwi::bitbox::is_bitbox02(info)
+}
+
+/// Stable identifier for a raw-HID device, used to diff bus snapshots between checks.
+fn hid_device_key(info: &DeviceInfo) -> String {
+    format!("{:?}-{}-{}", info.path(), info.vendor_id(), info.product_id())
+}
+
+#[derive(Default)]
+struct HidBusSnapshot {
+    present: std::collections::HashSet<String>,
+    missing_ticks: HashMap<String, u8>,
+    /// When each currently-enumerated id was first seen, so candidates can be settle-gated
+    /// independently of `present` (which tracks confirmed arrival/removal, not connect-readiness).
+    first_seen: HashMap<String, std::time::Instant>,
+}
+
+impl HidBusSnapshot {
+    /// Merge in the currently enumerated wallet interfaces, returning whether the confirmed set
+    /// of present devices changed (an arrival is confirmed immediately, a removal only once it's
+    /// been missing for `REMOVAL_DEBOUNCE_TICKS` consecutive checks).
+    fn update(&mut self, current: std::collections::HashSet<String>) -> bool {
+        let mut changed = false;
+        for id in &current {
+            self.missing_ticks.remove(id);
+            self.first_seen
+                .entry(id.clone())
+                .or_insert_with(std::time::Instant::now);
+            if self.present.insert(id.clone()) {
+                changed = true;
+            }
+        }
+        let missing: Vec<String> = self
+            .present
+            .iter()
+            .filter(|id| !current.contains(*id))
+            .cloned()
+            .collect();
+        for id in missing {
+            let ticks = self.missing_ticks.entry(id.clone()).or_insert(0);
+            *ticks += 1;
+            if *ticks >= REMOVAL_DEBOUNCE_TICKS {
+                self.present.remove(&id);
+                self.missing_ticks.remove(&id);
+                self.first_seen.remove(&id);
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Whether `id` has been continuously enumerated for at least `ATTACH_SETTLE_DELAY`, i.e.
+    /// it's safe to attempt opening it instead of racing a device that's still mid-boot.
+    fn is_settled(&self, id: &str) -> bool {
+        self.first_seen
+            .get(id)
+            .is_some_and(|t| t.elapsed() >= ATTACH_SETTLE_DELAY)
+    }
+}
+
+/// The raw-HID bus snapshot shared between `poll_hw` (which only asks whether something
+/// changed) and the per-vendor handlers in `hw_poll` (which ask whether a specific candidate
+/// has settled enough to attempt a connect).
+fn hid_snapshot() -> &'static Mutex<HidBusSnapshot> {
+    static HID_SNAPSHOT: std::sync::OnceLock<Mutex<HidBusSnapshot>> = std::sync::OnceLock::new();
+    HID_SNAPSHOT.get_or_init(|| Mutex::new(HidBusSnapshot::default()))
+}
+
+/// Check the HID bus for wallet interfaces and return whether the confirmed set of connected
+/// devices changed since the last check.
+fn hid_bus_changed(snapshot: &Mutex<HidBusSnapshot>) -> bool {
+    let api = match ledger::HidApi::new() {
+        Ok(api) => api,
+        Err(e) => {
+            debug!("Failed to open HID bus for hotplug detection: {}", e);
+            return false;
+        }
+    };
+    let current = api
+        .device_list()
+        .filter(|info| is_known_wallet_vid_pid(info) && is_wallet_interface(info))
+        .map(hid_device_key)
+        .collect();
+    snapshot.lock().unwrap().update(current)
+}
+
+/// Whether the raw-HID candidate identified by `id` has settled long enough to attempt a
+/// `connect`/`open_device` on it. Candidates that haven't been seen yet at all (e.g. a serial
+/// device, which isn't tracked here) are treated as settled so callers outside the HID bus
+/// aren't gated by a mechanism that doesn't apply to them.
+fn hid_candidate_settled(id: &str) -> bool {
+    // Device ids carry a `<kind>-` prefix in front of the `hid_device_key` the snapshot uses.
+    let key = id.split_once('-').map_or(id, |(_, key)| key);
+    let snapshot = hid_snapshot().lock().unwrap();
+    !snapshot.first_seen.contains_key(key) || snapshot.is_settled(key)
+}
+
+/// Retry `attempt` (an open/connect step that returns `None` on a transient failure) every
+/// [`POLLING_RETRY_INTERVAL`] until it succeeds or [`MAX_POLLING_DURATION`] elapses, so a
+/// device that isn't quite ready yet on the first try is retried within the same tick instead
+/// of being dropped until the next one.
+async fn retry_connect<T, F, Fut>(mut attempt: F) -> Option<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Option<T>>,
+{
+    let deadline = tokio::time::Instant::now() + MAX_POLLING_DURATION;
+    loop {
+        if let Some(value) = attempt().await {
+            return Some(value);
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return None;
+        }
+        tokio::time::sleep(POLLING_RETRY_INTERVAL).await;
+    }
+}
+
 pub async fn poll_hw(sender: mpsc::Sender<HwMessage>, dest: Destination) -> HwMessage {
     log::info!("poll_hw()");
+    let snapshot = hid_snapshot();
+
+    let deadline = tokio::time::Instant::now() + SERIAL_FALLBACK_INTERVAL;
+    loop {
+        tokio::time::sleep(HID_CHECK_INTERVAL).await;
+        if hid_bus_changed(snapshot) || tokio::time::Instant::now() >= deadline {
+            break;
+        }
+    }
     let _ = sender.send(HwMessage::Poll(dest.clone()));
-    tokio::time::sleep(Duration::from_secs(2)).await;
     HwMessage::Poll(dest)
 }
 
@@ -375,6 +845,10 @@ pub struct HwState {
     pub hws: Vec<HardwareWallet>,
     pub still: Vec<String>,
     pub receiver: Option<mpsc::Receiver<HwMessage>>,
+    pub pinserver: Option<PinServerConfig>,
+    /// When set, restricts polling to the one device this locator identifies instead of every
+    /// connected wallet, so a headless/automated flow can say "use exactly this device".
+    pub locator: Option<HardwareWalletLocator>,
 }
 
 pub async fn hw_refresh(mut state: HwState) -> (crate::message::Message, HwState) {
@@ -388,9 +862,13 @@ pub async fn hw_refresh(mut state: HwState) -> (crate::message::Message, HwState
         Destination::Installer => crate::message::Message::Install(Box::new(
             crate::installer::Message::HardwareWallets(msg),
         )),
-        Destination::SettingsWallet => todo!(),
-        Destination::Receive => todo!(),
-        Destination::Psbt => todo!(),
+        // Tagged with `dest` rather than folded into `Run(app::Message::HardwareWallets(..))`,
+        // so `SettingsWallet`, `Receive` and `Psbt` results carry proof of which one issued them
+        // instead of being claimed by "whichever panel is currently loaded" (see
+        // `Message::HardwareWalletPollResult`'s doc comment for the remaining per-panel wiring).
+        Destination::SettingsWallet | Destination::Receive | Destination::Psbt => {
+            crate::message::Message::HardwareWalletPollResult(dest, msg)
+        }
     };
 
     log::info!("msg -> {:#?}", msg);
@@ -416,17 +894,36 @@ async fn hw_poll(mut state: HwState) -> (HardwareWalletMessage, HwState) {
     poll_ledger_simulator(&mut state).await;
     poll_ledger(&mut state, &api).await;
 
+    let mut bitbox02_seen = 0usize;
+    let mut coldcard_seen = 0usize;
     for device_info in api.device_list() {
-        if async_hwi::bitbox::is_bitbox02(device_info)
-            && handle_bitbox02(&mut state, device_info, &api).await
-        {
-            continue;
+        if async_hwi::bitbox::is_bitbox02(device_info) {
+            let seen = bitbox02_seen;
+            bitbox02_seen += 1;
+            if handle_bitbox02(&mut state, device_info, &api, seen).await {
+                continue;
+            }
         }
         if device_info.vendor_id() == coldcard::api::COINKITE_VID
             && device_info.product_id() == coldcard::api::CKCC_PID
-            && handle_coldcard(&mut state, device_info, &api).await
         {
-            continue;
+            let seen = coldcard_seen;
+            coldcard_seen += 1;
+            if handle_coldcard(&mut state, device_info, &api, seen).await {
+                continue;
+            }
+        }
+    }
+
+    // A connected raw-HID device missing from this enumeration stays in `still` until the
+    // snapshot has debounced its removal (see `REMOVAL_DEBOUNCE_TICKS`).
+    {
+        let snapshot = hid_snapshot().lock().unwrap();
+        for id in &state.connected_supported_hws {
+            let key = id.split_once('-').map_or(id.as_str(), |(_, key)| key);
+            if !state.still.contains(id) && snapshot.present.contains(key) {
+                state.still.push(id.clone());
+            }
         }
     }
 
@@ -453,6 +950,30 @@ async fn hw_poll(mut state: HwState) -> (HardwareWalletMessage, HwState) {
         }
     }
 
+    // The PSBT screen sets `taproot` when the wallet's descriptor uses tapminiscript, so a
+    // device whose firmware can't sign for it is surfaced as unsupported rather than offered
+    // and failing the signing round-trip later.
+    if state.taproot {
+        for hw in &mut state.hws {
+            if let HardwareWallet::Supported {
+                id,
+                kind,
+                version,
+                ..
+            } = &hw
+            {
+                if !is_compatible_with_tapminiscript(kind, version.as_ref()) {
+                    *hw = HardwareWallet::Unsupported {
+                        id: id.clone(),
+                        kind: *kind,
+                        version: version.clone(),
+                        reason: UnsupportedReason::Method("tapminiscript"),
+                    };
+                }
+            }
+        }
+    }
+
     state.connected_supported_hws = state
         .still
         .iter()
@@ -474,6 +995,9 @@ async fn hw_poll(mut state: HwState) -> (HardwareWalletMessage, HwState) {
 }
 
 pub async fn poll_specter_simulator(state: &mut HwState) {
+    if !HardwareWalletLocator::allows_attempt(state.locator.as_ref(), DeviceKind::SpecterSimulator, 0) {
+        return;
+    }
     match specter::SpecterSimulator::try_connect().await {
         Ok(device) => {
             let id = "specter-simulator".to_string();
@@ -481,7 +1005,15 @@ pub async fn poll_specter_simulator(state: &mut HwState) {
                 state.still.push(id);
             } else {
                 match HardwareWallet::new(id, Arc::new(device), Some(&state.keys_aliases)).await {
-                    Ok(hw) => state.hws.push(hw),
+                    Ok(hw) => {
+                        if HardwareWalletLocator::accepts(
+                            state.locator.as_ref(),
+                            DeviceKind::SpecterSimulator,
+                            hw.fingerprint(),
+                        ) {
+                            state.hws.push(hw);
+                        }
+                    }
                     Err(e) => {
                         debug!("{}", e);
                     }
@@ -496,37 +1028,51 @@ pub async fn poll_specter_simulator(state: &mut HwState) {
 }
 
 pub async fn poll_specter(state: &mut HwState) {
+    if !HardwareWalletLocator::allows_kind(state.locator.as_ref(), DeviceKind::Specter) {
+        return;
+    }
     match specter::SerialTransport::enumerate_potential_ports() {
         Ok(ports) => {
+            let mut seen = 0usize;
             for port in ports {
                 let id = format!("specter-{}", port);
                 if state.connected_supported_hws.contains(&id) {
                     state.still.push(id);
-                } else {
-                    match specter::Specter::<specter::SerialTransport>::new(port.clone()) {
-                        Err(e) => {
-                            warn!("{}", e);
-                        }
-                        Ok(device) => {
-                            if tokio::time::timeout(
-                                std::time::Duration::from_millis(500),
-                                device.fingerprint(),
-                            )
-                            .await
-                            .is_ok()
-                            {
-                                match HardwareWallet::new(
-                                    id,
-                                    Arc::new(device),
-                                    Some(&state.keys_aliases),
-                                )
+                    continue;
+                }
+                let allowed =
+                    HardwareWalletLocator::allows_attempt(state.locator.as_ref(), DeviceKind::Specter, seen);
+                seen += 1;
+                if !allowed {
+                    continue;
+                }
+                match specter::Specter::<specter::SerialTransport>::new(port.clone()) {
+                    Err(e) => {
+                        warn!("{}", e);
+                    }
+                    Ok(device) => {
+                        if tokio::time::timeout(
+                            std::time::Duration::from_millis(500),
+                            device.fingerprint(),
+                        )
+                        .await
+                        .is_ok()
+                        {
+                            match HardwareWallet::new(id, Arc::new(device), Some(&state.keys_aliases))
                                 .await
-                                {
-                                    Ok(hw) => state.hws.push(hw),
-                                    Err(e) => {
-                                        debug!("{}", e);
+                            {
+                                Ok(hw) => {
+                                    if HardwareWalletLocator::accepts(
+                                        state.locator.as_ref(),
+                                        DeviceKind::Specter,
+                                        hw.fingerprint(),
+                                    ) {
+                                        state.hws.push(hw);
                                     }
                                 }
+                                Err(e) => {
+                                    debug!("{}", e);
+                                }
                             }
                         }
                     }
@@ -537,34 +1083,63 @@ pub async fn poll_specter(state: &mut HwState) {
     }
 }
 
+/// Enumerate Blockstream Jade over its serial transport, run the CBOR handshake and report it
+/// the same way the other device kinds are: `Locked` until the `pinserver` auth exchange
+/// completes (see [`with_pinserver`] for routing that through a custom server/proxy), then
+/// `Supported` once [`handle_jade_device`] has the master fingerprint and wallet-registration
+/// status.
+///
+/// Honors `state.locator`: skipped entirely for another device kind, each port is pre-filtered
+/// with `HardwareWalletLocator::allows_attempt`, and the result is kept only if
+/// `HardwareWalletLocator::accepts` it.
 pub async fn poll_jade(state: &mut HwState) {
+    if !HardwareWalletLocator::allows_kind(state.locator.as_ref(), DeviceKind::Jade) {
+        return;
+    }
     match jade::SerialTransport::enumerate_potential_ports() {
         Ok(ports) => {
+            let mut seen = 0usize;
             for port in ports {
                 let id = format!("jade-{}", port);
                 if state.connected_supported_hws.contains(&id) {
                     state.still.push(id);
-                } else {
-                    match jade::SerialTransport::new(port) {
-                        Err(e) => {
-                            warn!("{:?}", e);
-                        }
-                        Ok(device) => {
-                            match handle_jade_device(
-                                id,
-                                state.network,
-                                Jade::new(device).with_network(state.network),
-                                state.wallet.as_ref().map(|w| w.as_ref()),
-                                Some(&state.keys_aliases),
-                            )
-                            .await
-                            {
-                                Ok(hw) => {
+                    continue;
+                }
+                let allowed =
+                    HardwareWalletLocator::allows_attempt(state.locator.as_ref(), DeviceKind::Jade, seen);
+                seen += 1;
+                if !allowed {
+                    continue;
+                }
+                match jade::SerialTransport::new(port) {
+                    Err(e) => {
+                        warn!("{:?}", e);
+                    }
+                    Ok(device) => {
+                        let device = with_pinserver(
+                            Jade::new(device).with_network(state.network),
+                            state.pinserver.as_ref(),
+                        );
+                        match handle_jade_device(
+                            id,
+                            state.network,
+                            device,
+                            state.wallet.as_ref().map(|w| w.as_ref()),
+                            Some(&state.keys_aliases),
+                        )
+                        .await
+                        {
+                            Ok(hw) => {
+                                if HardwareWalletLocator::accepts(
+                                    state.locator.as_ref(),
+                                    DeviceKind::Jade,
+                                    hw.fingerprint(),
+                                ) {
                                     state.hws.push(hw);
                                 }
-                                Err(e) => {
-                                    warn!("{:?}", e);
-                                }
+                            }
+                            Err(e) => {
+                                warn!("{:?}", e);
                             }
                         }
                     }
@@ -664,6 +1239,9 @@ async fn handle_jade_device(
 }
 
 pub async fn poll_ledger_simulator(state: &mut HwState) {
+    if !HardwareWalletLocator::allows_attempt(state.locator.as_ref(), DeviceKind::Ledger, 0) {
+        return;
+    }
     match ledger::LedgerSimulator::try_connect().await {
         Ok(mut device) => {
             let id = "ledger-simulator".to_string();
@@ -691,15 +1269,21 @@ pub async fn poll_ledger_simulator(state: &mut HwState) {
                                     registered = true;
                                 }
                             }
-                            state.hws.push(HardwareWallet::Supported {
-                                id,
-                                kind: device.device_kind(),
-                                fingerprint,
-                                device: Arc::new(device),
-                                version,
-                                registered: Some(registered),
-                                alias: state.keys_aliases.get(&fingerprint).cloned(),
-                            });
+                            if HardwareWalletLocator::accepts(
+                                state.locator.as_ref(),
+                                DeviceKind::Ledger,
+                                Some(fingerprint),
+                            ) {
+                                state.hws.push(HardwareWallet::Supported {
+                                    id,
+                                    kind: device.device_kind(),
+                                    fingerprint,
+                                    device: Arc::new(device),
+                                    version,
+                                    registered: Some(registered),
+                                    alias: state.keys_aliases.get(&fingerprint).cloned(),
+                                });
+                            }
                         } else {
                             state.hws.push(HardwareWallet::Unsupported {
                                 id,
@@ -711,14 +1295,12 @@ pub async fn poll_ledger_simulator(state: &mut HwState) {
                             });
                         }
                     }
-                    Err(_) => {
+                    Err(e) => {
                         state.hws.push(HardwareWallet::Unsupported {
                             id,
                             kind: device.device_kind(),
                             version: None,
-                            reason: UnsupportedReason::Version {
-                                minimal_supported_version: "2.1.0",
-                            },
+                            reason: UnsupportedReason::Error(HwPollError::from(&e)),
                         });
                     }
                 }
@@ -732,6 +1314,10 @@ pub async fn poll_ledger_simulator(state: &mut HwState) {
 }
 
 pub async fn poll_ledger(state: &mut HwState, api: &HidApi) {
+    if !HardwareWalletLocator::allows_kind(state.locator.as_ref(), DeviceKind::Ledger) {
+        return;
+    }
+    let mut seen = 0usize;
     for detected in ledger::Ledger::<ledger::TransportHID>::enumerate(api) {
         let id = format!(
             "ledger-{:?}-{}-{}",
@@ -744,6 +1330,14 @@ pub async fn poll_ledger(state: &mut HwState, api: &HidApi) {
             state.still.push(id);
             continue;
         }
+        if !hid_candidate_settled(&id) {
+            continue;
+        }
+        let allowed = HardwareWalletLocator::allows_attempt(state.locator.as_ref(), DeviceKind::Ledger, seen);
+        seen += 1;
+        if !allowed {
+            continue;
+        }
         match ledger::Ledger::<ledger::TransportHID>::connect(api, detected) {
             Ok(mut device) => match device.get_master_fingerprint().await {
                 Ok(fingerprint) => {
@@ -766,15 +1360,21 @@ pub async fn poll_ledger(state: &mut HwState, api: &HidApi) {
                                 registered = true;
                             }
                         }
-                        state.hws.push(HardwareWallet::Supported {
-                            id,
-                            kind: device.device_kind(),
-                            fingerprint,
-                            device: Arc::new(device),
-                            version,
-                            registered: Some(registered),
-                            alias: state.keys_aliases.get(&fingerprint).cloned(),
-                        });
+                        if HardwareWalletLocator::accepts(
+                            state.locator.as_ref(),
+                            DeviceKind::Ledger,
+                            Some(fingerprint),
+                        ) {
+                            state.hws.push(HardwareWallet::Supported {
+                                id,
+                                kind: device.device_kind(),
+                                fingerprint,
+                                device: Arc::new(device),
+                                version,
+                                registered: Some(registered),
+                                alias: state.keys_aliases.get(&fingerprint).cloned(),
+                            });
+                        }
                     } else {
                         state.hws.push(HardwareWallet::Unsupported {
                             id,
@@ -786,14 +1386,12 @@ pub async fn poll_ledger(state: &mut HwState, api: &HidApi) {
                         });
                     }
                 }
-                Err(_) => {
+                Err(e) => {
                     state.hws.push(HardwareWallet::Unsupported {
                         id,
                         kind: device.device_kind(),
                         version: None,
-                        reason: UnsupportedReason::Version {
-                            minimal_supported_version: "2.1.0",
-                        },
+                        reason: UnsupportedReason::Error(HwPollError::from(&e)),
                     });
                 }
             },
@@ -805,7 +1403,12 @@ pub async fn poll_ledger(state: &mut HwState, api: &HidApi) {
     }
 }
 
-pub async fn handle_bitbox02(state: &mut HwState, device_info: &DeviceInfo, api: &HidApi) -> bool {
+pub async fn handle_bitbox02(
+    state: &mut HwState,
+    device_info: &DeviceInfo,
+    api: &HidApi,
+    seen_of_kind: usize,
+) -> bool {
     let id = format!(
         "bitbox-{:?}-{}-{}",
         device_info.path(),
@@ -816,28 +1419,45 @@ pub async fn handle_bitbox02(state: &mut HwState, device_info: &DeviceInfo, api:
         state.still.push(id);
         return true;
     }
-    if let Ok(device) = device_info.open_device(api) {
-        if let Ok(device) = PairingBitbox02::connect(
+    if !HardwareWalletLocator::allows_attempt(state.locator.as_ref(), DeviceKind::BitBox02, seen_of_kind) {
+        return false;
+    }
+    if !hid_candidate_settled(&id) {
+        return false;
+    }
+    let device = retry_connect(|| async {
+        let device = device_info.open_device(api).ok()?;
+        PairingBitbox02::connect(
             device,
             Some(Box::new(settings::global::PersistedBitboxNoiseConfig::new(
                 &state.datadir_path,
             ))),
         )
         .await
-        {
-            state.hws.push(HardwareWallet::Locked {
-                id,
-                kind: DeviceKind::BitBox02,
-                pairing_code: device.pairing_code().map(|s| s.replace('\n', " ")),
-                device: Arc::new(Mutex::new(Some(LockedDevice::BitBox02(Box::new(device))))),
-            });
-            return true;
-        }
+        .ok()
+    })
+    .await;
+    if let Some(device) = device {
+        // The fingerprint isn't known until the device is unlocked, so a fingerprint-based
+        // locator can't filter here: `allows_attempt` above already matched the kind, and the
+        // device is listed whichever fingerprint the locator names.
+        state.hws.push(HardwareWallet::Locked {
+            id,
+            kind: DeviceKind::BitBox02,
+            pairing_code: device.pairing_code().map(|s| s.replace('\n', " ")),
+            device: Arc::new(Mutex::new(Some(LockedDevice::BitBox02(Box::new(device))))),
+        });
+        return true;
     }
     false
 }
 
-pub async fn handle_coldcard(state: &mut HwState, device_info: &DeviceInfo, api: &HidApi) -> bool {
+pub async fn handle_coldcard(
+    state: &mut HwState,
+    device_info: &DeviceInfo,
+    api: &HidApi,
+    seen_of_kind: usize,
+) -> bool {
     let id = format!(
         "coldcard-{:?}-{}-{}",
         device_info.path(),
@@ -848,10 +1468,22 @@ pub async fn handle_coldcard(state: &mut HwState, device_info: &DeviceInfo, api:
         state.still.push(id);
         return true;
     }
+    if !HardwareWalletLocator::allows_attempt(state.locator.as_ref(), DeviceKind::Coldcard, seen_of_kind) {
+        return false;
+    }
+    if !hid_candidate_settled(&id) {
+        return false;
+    }
     if let Some(sn) = device_info.serial_number() {
-        if let Ok((cc, _)) = coldcard::api::Coldcard::open(AsRefWrap { inner: api }, sn, None) {
+        let cc = retry_connect(|| async {
+            coldcard::api::Coldcard::open(AsRefWrap { inner: api }, sn, None)
+                .ok()
+                .map(|(cc, _)| cc)
+        })
+        .await;
+        if let Some(cc) = cc {
             match HardwareWallet::new(
-                id,
+                id.clone(),
                 if let Some(wallet) = &state.wallet {
                     coldcard::Coldcard::from(cc)
                         .with_wallet_name(wallet.name.clone())
@@ -863,9 +1495,23 @@ pub async fn handle_coldcard(state: &mut HwState, device_info: &DeviceInfo, api:
             )
             .await
             {
-                Err(e) => tracing::error!("Failed to connect to coldcard: {}", e),
+                Err(e) => {
+                    tracing::error!("Failed to connect to coldcard: {}", e);
+                    state.hws.push(HardwareWallet::Unsupported {
+                        id,
+                        kind: DeviceKind::Coldcard,
+                        version: None,
+                        reason: UnsupportedReason::Error(HwPollError::from(&e)),
+                    });
+                }
                 Ok(hw) => {
-                    state.hws.push(hw);
+                    if HardwareWalletLocator::accepts(
+                        state.locator.as_ref(),
+                        DeviceKind::Coldcard,
+                        hw.fingerprint(),
+                    ) {
+                        state.hws.push(hw);
+                    }
                     return true;
                 }
             };
@@ -900,44 +1546,110 @@ fn ledger_version_supported(version: Option<&Version>) -> bool {
     }
 }
 
-// Kind and minimal version of devices supporting tapminiscript.
-// We cannot use a lazy_static HashMap yet, because DeviceKind does not implement Hash.
-const DEVICES_COMPATIBLE_WITH_TAPMINISCRIPT: [(DeviceKind, Option<Version>); 4] = [
-    (
-        DeviceKind::Ledger,
-        Some(Version {
-            major: 2,
-            minor: 2,
-            patch: 0,
-            prerelease: None,
-        }),
-    ),
-    (DeviceKind::Specter, None),
-    (DeviceKind::SpecterSimulator, None),
-    (
-        DeviceKind::Coldcard,
-        Some(Version {
-            major: 6,
-            minor: 3,
-            patch: 3,
-            prerelease: None,
-        }),
-    ),
-];
+/// A device capability gated on firmware version. Only tapminiscript is checked today, but this
+/// is where e.g. support for external-signer PSBT fields would join it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeviceCapability {
+    TapMiniscript,
+}
+
+/// `async_hwi::DeviceKind` doesn't implement `Hash`, so this local stand-in mirroring the
+/// variants we gate capabilities on is used as the registry's map key instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum DeviceKindKey {
+    Ledger,
+    Coldcard,
+    BitBox02,
+    Jade,
+    Specter,
+    SpecterSimulator,
+}
+
+impl DeviceKindKey {
+    fn from_kind(kind: &DeviceKind) -> Option<Self> {
+        Some(match kind {
+            DeviceKind::Ledger => Self::Ledger,
+            DeviceKind::Coldcard => Self::Coldcard,
+            DeviceKind::BitBox02 => Self::BitBox02,
+            DeviceKind::Jade => Self::Jade,
+            DeviceKind::Specter => Self::Specter,
+            DeviceKind::SpecterSimulator => Self::SpecterSimulator,
+            _ => return None,
+        })
+    }
+}
+
+/// Minimum firmware version required for each (device kind, capability) pair, or `None` when
+/// every version of that device supports it.
+fn capability_registry() -> &'static HashMap<(DeviceKindKey, DeviceCapability), Option<Version>> {
+    static REGISTRY: std::sync::OnceLock<HashMap<(DeviceKindKey, DeviceCapability), Option<Version>>> =
+        std::sync::OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        use DeviceCapability::TapMiniscript;
+        use DeviceKindKey::*;
+        HashMap::from([
+            (
+                (Ledger, TapMiniscript),
+                Some(Version {
+                    major: 2,
+                    minor: 2,
+                    patch: 0,
+                    prerelease: None,
+                }),
+            ),
+            ((Specter, TapMiniscript), None),
+            ((SpecterSimulator, TapMiniscript), None),
+            (
+                (Coldcard, TapMiniscript),
+                Some(Version {
+                    major: 6,
+                    minor: 3,
+                    patch: 3,
+                    prerelease: None,
+                }),
+            ),
+            (
+                (Jade, TapMiniscript),
+                Some(Version {
+                    major: 1,
+                    minor: 0,
+                    patch: 31,
+                    prerelease: None,
+                }),
+            ),
+            (
+                (BitBox02, TapMiniscript),
+                Some(Version {
+                    major: 9,
+                    minor: 21,
+                    patch: 0,
+                    prerelease: None,
+                }),
+            ),
+        ])
+    })
+}
+
+/// Whether a connected device of `device_kind` running `version` supports `capability`.
+/// A device kind the registry doesn't know about never supports anything queried this way.
+pub fn device_supports_capability(
+    device_kind: &DeviceKind,
+    version: Option<&Version>,
+    capability: DeviceCapability,
+) -> bool {
+    let Some(key) = DeviceKindKey::from_kind(device_kind) else {
+        return false;
+    };
+    match capability_registry().get(&(key, capability)) {
+        Some(Some(minimal_version)) => version.is_some_and(|v| v >= minimal_version),
+        Some(None) => true,
+        None => false,
+    }
+}
 
 pub fn is_compatible_with_tapminiscript(
     device_kind: &DeviceKind,
     version: Option<&Version>,
 ) -> bool {
-    DEVICES_COMPATIBLE_WITH_TAPMINISCRIPT
-        .iter()
-        .any(|(kind, minimal_version)| {
-            device_kind == kind
-                && match (version, minimal_version) {
-                    (Some(v1), Some(v2)) => v1 >= v2,
-                    (None, Some(_)) => false,
-                    (Some(_), None) => true,
-                    (None, None) => true,
-                }
-        })
+    device_supports_capability(device_kind, version, DeviceCapability::TapMiniscript)
 }
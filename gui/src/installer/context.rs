@@ -5,6 +5,7 @@ use std::{path::PathBuf, sync::mpsc};
 use crate::hw::HwMessage;
 use crate::{
     app::{
+        config::ConfigError,
         settings::{KeySetting, Settings, WalletSetting},
         wallet::wallet_name,
     },
@@ -19,11 +20,203 @@ use liana::{
     descriptors::LianaDescriptor,
     miniscript::bitcoin,
 };
+use serde::{Deserialize, Serialize};
+
+/// The daemon backend the installer is configuring the wallet to use.
+///
+/// `Bitcoind` is the long-standing default: a full node reachable over RPC, either external or
+/// one Liana manages itself (see `bitcoind_is_external`/`internal_bitcoind_config`). `CompactFilters`
+/// lets a user without a full node sync over the P2P network instead, verifying BIP157/158 filters
+/// against a small set of peers rather than trusting an RPC cookie.
+#[derive(Clone)]
+pub enum BackendConfig {
+    Bitcoind(BitcoindConfig),
+    CompactFilters(CompactFilterConfig),
+}
+
+/// Peers to fetch BIP157 compact filters and filter headers from. The installer's light-client
+/// path collects these instead of an RPC cookie/address.
+#[derive(Debug, Clone)]
+pub struct CompactFilterConfig {
+    pub peers: Vec<std::net::SocketAddr>,
+}
+
+/// Bitcoind ZMQ endpoints the daemon can subscribe to for near-instant block/transaction
+/// notification instead of relying solely on `BitcoinConfig::poll_interval_secs`. Any topic left
+/// unset falls back to the timed poll for that kind of event.
+///
+/// This installer only collects and validates these endpoints (see [`validate_zmq_endpoint`]);
+/// opening the actual ZMQ subscriber socket and triggering a rescan/poll off its notifications is
+/// lianad's job, not this GUI's, and `liana::config::BitcoindConfig` has no field to carry these
+/// to it yet (see the warning in `extract_daemon_config` below).
+#[derive(Debug, Clone, Default)]
+pub struct ZmqConfig {
+    pub raw_block: Option<String>,
+    pub hash_block: Option<String>,
+    pub sequence: Option<String>,
+}
+
+/// Why a user-supplied ZMQ endpoint was rejected before being stored in a [`ZmqConfig`] field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ZmqEndpointError {
+    MissingScheme,
+    UnsupportedScheme(String),
+    MissingAddress,
+}
+
+impl std::fmt::Display for ZmqEndpointError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::MissingScheme => write!(f, "ZMQ endpoint is missing a \"scheme://\" prefix"),
+            Self::UnsupportedScheme(scheme) => write!(
+                f,
+                "bitcoind's ZMQ interface doesn't support the \"{}\" transport, only tcp and ipc",
+                scheme
+            ),
+            Self::MissingAddress => write!(f, "ZMQ endpoint is missing an address after the scheme"),
+        }
+    }
+}
+
+impl std::error::Error for ZmqEndpointError {}
+
+/// Sanity-check a ZMQ endpoint string (e.g. `tcp://127.0.0.1:28332`) before storing it in a
+/// [`ZmqConfig`] field. bitcoind's ZMQ notification interface only supports the `tcp` and `ipc`
+/// transports.
+pub fn validate_zmq_endpoint(endpoint: &str) -> Result<(), ZmqEndpointError> {
+    let (scheme, address) = endpoint
+        .split_once("://")
+        .ok_or(ZmqEndpointError::MissingScheme)?;
+    match scheme {
+        "tcp" | "ipc" => {}
+        other => return Err(ZmqEndpointError::UnsupportedScheme(other.to_string())),
+    }
+    if address.is_empty() {
+        return Err(ZmqEndpointError::MissingAddress);
+    }
+    Ok(())
+}
+
+/// Bitcoin Core refuses to load an `-asmap` file larger than this.
+const MAX_ASMAP_FILE_SIZE: u64 = 4 * 1024 * 1024;
+
+/// Sanity-check a compiled IP->ASN asmap file before passing its path as `-asmap` to the internal
+/// bitcoind. A compiled asmap is a raw addrman instruction stream with no magic number or version
+/// field to check, so the strongest validation possible ahead of actually interpreting it is
+/// structural: present, non-empty, and within the size bitcoind itself refuses to load.
+///
+/// Reuses `ConfigError::InvalidField` rather than a standalone error enum, the same way
+/// `Config::theme` reports a bad palette file: both are "this field in the installer/config
+/// flow points at something unusable" errors, not a new category of failure.
+pub fn validate_asmap_file(path: &std::path::Path) -> Result<(), ConfigError> {
+    let metadata = std::fs::metadata(path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => {
+            ConfigError::InvalidField("asmap_path", "Asmap file not found".to_string())
+        }
+        _ => ConfigError::InvalidField(
+            "asmap_path",
+            format!("Could not read asmap file: {}", e),
+        ),
+    })?;
+    match metadata.len() {
+        0 => Err(ConfigError::InvalidField(
+            "asmap_path",
+            "Asmap file is empty".to_string(),
+        )),
+        len if len > MAX_ASMAP_FILE_SIZE => Err(ConfigError::InvalidField(
+            "asmap_path",
+            format!(
+                "Asmap file is {} bytes, bitcoind refuses anything over {}",
+                len, MAX_ASMAP_FILE_SIZE
+            ),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Where a bundled `ip_asn.map` ships alongside the installer binary, if the packaging for this
+/// platform puts one there. Checked in order; the first candidate that passes
+/// [`validate_asmap_file`] wins, so users get ASN-based peer bucketing out of the box without
+/// hunting down and supplying their own compiled asmap.
+fn bundled_asmap_candidates() -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+    if let Ok(exe) = std::env::current_exe() {
+        if let Some(dir) = exe.parent() {
+            candidates.push(dir.join("ip_asn.map"));
+        }
+    }
+    candidates
+}
+
+/// First of `candidates` that [`validate_asmap_file`] accepts, or `None` if none do. Split out of
+/// [`default_asmap_path`] so the selection logic is testable without depending on
+/// `std::env::current_exe`.
+fn first_valid_asmap_path(candidates: Vec<PathBuf>) -> Option<PathBuf> {
+    candidates
+        .into_iter()
+        .find(|path| validate_asmap_file(path).is_ok())
+}
+
+/// A bundled asmap to default `Context::asmap_path` to, if this platform's packaging shipped one
+/// next to the binary. Installer steps are still free to let the user point at their own file
+/// instead; this only supplies a default so ASN-based peer bucketing works without that extra
+/// step for the common case.
+pub fn default_asmap_path() -> Option<PathBuf> {
+    first_valid_asmap_path(bundled_asmap_candidates())
+}
+
+/// Progress of an assumeutxo snapshot load on the internal bitcoind: the node is usable right
+/// away at `snapshot_height`, while background validation walks the full history back up to it.
+/// Spending flows should stay blocked (or clearly warn) until `validated_height` reaches
+/// `snapshot_height`.
+///
+/// Threading the snapshot file itself into `loadtxoutset` at startup is `InternalBitcoindConfig`'s
+/// and the `bitcoind` launcher's job; this only carries the progress back out for the GUI to
+/// display, since neither of those live in this part of the tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssumeUtxoProgress {
+    pub snapshot_height: i32,
+    pub validated_height: i32,
+}
+
+impl AssumeUtxoProgress {
+    pub fn is_fully_validated(&self) -> bool {
+        self.validated_height >= self.snapshot_height
+    }
+}
+
+impl Context {
+    /// Whether spending should stay blocked (or clearly warned about) because the internal
+    /// bitcoind is still background-validating an assumeutxo snapshot. `false` once there's no
+    /// snapshot in progress, or once it's fully validated.
+    pub fn assumeutxo_blocks_spending(&self) -> bool {
+        self.assumeutxo_progress
+            .is_some_and(|progress| !progress.is_fully_validated())
+    }
+}
 
 #[derive(Clone)]
 pub struct Context {
     pub bitcoin_config: BitcoinConfig,
+    /// Deprecated alias kept alongside `backend_config` so installer steps that still read/set
+    /// this field directly (elsewhere in the installer, not in this file) keep compiling. Set
+    /// it and `backend_config` will pick it up as `BackendConfig::Bitcoind` wherever
+    /// `backend_config` itself is left unset; new code should just use `backend_config`.
     pub bitcoind_config: Option<BitcoindConfig>,
+    pub backend_config: Option<BackendConfig>,
+    /// Populated by the installer's bitcoind step when the user supplies ZMQ endpoints; `None`
+    /// keeps the existing poll-only behavior.
+    pub zmq_config: Option<ZmqConfig>,
+    /// Set while the internal bitcoind is still background-validating an assumeutxo snapshot;
+    /// cleared once `AssumeUtxoProgress::is_fully_validated` is true.
+    pub assumeutxo_progress: Option<AssumeUtxoProgress>,
+    /// Path to a compiled asmap file, validated with [`validate_asmap_file`], to launch the
+    /// internal bitcoind with `-asmap=<path>` for ASN-based peer bucketing instead of raw IP
+    /// ranges. Defaults to [`default_asmap_path`]'s bundled file when packaging shipped one;
+    /// `None` (no bundled file, and the user hasn't picked their own) keeps addrman's default
+    /// IP-range buckets. Threading this into the actual launch command is
+    /// `InternalBitcoindConfig`'s and the bitcoind launcher's job.
+    pub asmap_path: Option<PathBuf>,
     pub descriptor: Option<LianaDescriptor>,
     pub keys: Vec<KeySetting>,
     pub hws: Vec<(DeviceKind, bitcoin::bip32::Fingerprint, Option<[u8; 32]>)>,
@@ -52,6 +245,10 @@ impl Context {
             hws: Vec::new(),
             keys: Vec::new(),
             bitcoind_config: None,
+            backend_config: None,
+            zmq_config: None,
+            assumeutxo_progress: None,
+            asmap_path: default_asmap_path(),
             descriptor: None,
             data_dir,
             hw_is_used: false,
@@ -93,6 +290,29 @@ impl Context {
     }
 
     pub fn extract_daemon_config(&self) -> Config {
+        let bitcoind_config = match &self.backend_config {
+            Some(BackendConfig::Bitcoind(config)) => Some(config.clone()),
+            // lianad's on-disk `Config` only has a slot for `bitcoind_config`; it has no shape
+            // yet for a compact-filter backend. Once it does, this arm threads `config` through
+            // the same way; for now the daemon simply isn't told about this backend choice.
+            Some(BackendConfig::CompactFilters(_)) => None,
+            // Nothing set `backend_config`; fall back to the deprecated `bitcoind_config` field
+            // in case an older installer step populated that one directly instead.
+            None => self.bitcoind_config.clone(),
+        };
+        // `liana::config::BitcoindConfig` is an external crate type this installer can't add
+        // fields to, and it has no slot for ZMQ endpoints yet, so `self.zmq_config` can't be
+        // merged into `bitcoind_config` here. Warn rather than silently dropping it, so a user
+        // who configured ZMQ endpoints in the installer isn't left wondering why the daemon
+        // still only polls.
+        if let Some(zmq) = &self.zmq_config {
+            if zmq.raw_block.is_some() || zmq.hash_block.is_some() || zmq.sequence.is_some() {
+                log::warn!(
+                    "ZMQ endpoints were configured in the installer but lianad's BitcoindConfig \
+                     has no field for them yet; the daemon will fall back to polling."
+                );
+            }
+        }
         Config {
             #[cfg(unix)]
             daemon: false,
@@ -100,7 +320,140 @@ impl Context {
             main_descriptor: self.descriptor.clone().unwrap(),
             data_dir: Some(self.data_dir.clone()),
             bitcoin_config: self.bitcoin_config.clone(),
-            bitcoind_config: self.bitcoind_config.clone(),
+            bitcoind_config,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("liana-asmap-test-{}-{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn zmq_endpoint_accepts_tcp_and_ipc() {
+        assert_eq!(validate_zmq_endpoint("tcp://127.0.0.1:28332"), Ok(()));
+        assert_eq!(validate_zmq_endpoint("ipc:///tmp/bitcoind.zmq"), Ok(()));
+    }
+
+    #[test]
+    fn zmq_endpoint_rejects_missing_scheme() {
+        assert_eq!(
+            validate_zmq_endpoint("127.0.0.1:28332"),
+            Err(ZmqEndpointError::MissingScheme)
+        );
+    }
+
+    #[test]
+    fn zmq_endpoint_rejects_unsupported_scheme() {
+        assert_eq!(
+            validate_zmq_endpoint("http://127.0.0.1:28332"),
+            Err(ZmqEndpointError::UnsupportedScheme("http".to_string()))
+        );
+    }
+
+    #[test]
+    fn zmq_endpoint_rejects_missing_address() {
+        assert_eq!(
+            validate_zmq_endpoint("tcp://"),
+            Err(ZmqEndpointError::MissingAddress)
+        );
+    }
+
+    #[test]
+    fn rejects_missing_file() {
+        let path = temp_path("missing");
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(
+            validate_asmap_file(&path),
+            Err(ConfigError::InvalidField(
+                "asmap_path",
+                "Asmap file not found".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn rejects_empty_file() {
+        let path = temp_path("empty");
+        std::fs::File::create(&path).unwrap();
+        assert_eq!(
+            validate_asmap_file(&path),
+            Err(ConfigError::InvalidField(
+                "asmap_path",
+                "Asmap file is empty".to_string()
+            ))
+        );
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn rejects_oversized_file() {
+        let path = temp_path("oversized");
+        let mut file = std::fs::File::create(&path).unwrap();
+        file.write_all(&vec![0u8; (MAX_ASMAP_FILE_SIZE + 1) as usize])
+            .unwrap();
+        assert_eq!(
+            validate_asmap_file(&path),
+            Err(ConfigError::InvalidField(
+                "asmap_path",
+                format!(
+                    "Asmap file is {} bytes, bitcoind refuses anything over {}",
+                    MAX_ASMAP_FILE_SIZE + 1,
+                    MAX_ASMAP_FILE_SIZE
+                )
+            ))
+        );
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn accepts_non_empty_file_within_limit() {
+        let path = temp_path("ok");
+        std::fs::write(&path, b"some asmap bytes").unwrap();
+        assert!(validate_asmap_file(&path).is_ok());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn default_asmap_path_picks_the_first_candidate_that_validates() {
+        let missing = temp_path("bundled-missing");
+        let _ = std::fs::remove_file(&missing);
+        let valid = temp_path("bundled-valid");
+        std::fs::write(&valid, b"some asmap bytes").unwrap();
+        assert_eq!(
+            first_valid_asmap_path(vec![missing, valid.clone()]),
+            Some(valid.clone())
+        );
+        let _ = std::fs::remove_file(&valid);
+    }
+
+    #[test]
+    fn default_asmap_path_is_none_when_no_candidate_validates() {
+        let missing = temp_path("bundled-none");
+        let _ = std::fs::remove_file(&missing);
+        assert_eq!(first_valid_asmap_path(vec![missing]), None);
+    }
+
+    #[test]
+    fn assumeutxo_progress_is_not_fully_validated_until_caught_up() {
+        let progress = AssumeUtxoProgress {
+            snapshot_height: 800_000,
+            validated_height: 400_000,
+        };
+        assert!(!progress.is_fully_validated());
+    }
+
+    #[test]
+    fn assumeutxo_progress_is_fully_validated_once_caught_up() {
+        let progress = AssumeUtxoProgress {
+            snapshot_height: 800_000,
+            validated_height: 800_000,
+        };
+        assert!(progress.is_fully_validated());
+    }
+}
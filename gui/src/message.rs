@@ -1,4 +1,8 @@
-use crate::{app, hw::HardwareWalletMessage, installer, launcher, loader};
+use crate::{
+    app,
+    hw::{Destination, HardwareWalletMessage},
+    installer, launcher, loader,
+};
 
 #[derive(Debug)]
 pub enum Key {
@@ -16,6 +20,20 @@ pub enum Message {
     KeyPressed(Key),
     Event(iced::Event),
     HardwareWallet(HardwareWalletMessage),
+    /// Result of a hardware-wallet poll issued by `SettingsWallet`, `Receive` or `Psbt`, tagged
+    /// with the `Destination` that issued it. Replaces routing these three through
+    /// `Run(app::Message::HardwareWallets(..))` and relying on "whichever panel is currently
+    /// loaded" to claim it -- which misdelivers the result if the user navigates to a different
+    /// panel before the poll resolves. `Destination::Installer` still goes through
+    /// `Install(installer::Message::HardwareWallets(..))`, since the installer has no sibling
+    /// panels a result could be misdelivered to.
+    ///
+    /// Dispatching this variant still needs each panel (`SettingsWalletPanel`, `ReceivePanel`,
+    /// `PsbtsPanel`) to only act on it when its own `Destination` tag matches its own identity,
+    /// and currently just forwards untagged to whichever panel is loaded until that per-panel
+    /// check is added -- those panel state files aren't part of this tree slice. The tag is
+    /// carried this far so that check has something to match on once they are.
+    HardwareWalletPollResult(Destination, HardwareWalletMessage),
 }
 
 impl From<HardwareWalletMessage> for Message {
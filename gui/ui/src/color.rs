@@ -113,3 +113,125 @@ pub const TEST_MENU_BUTTON: Color = HOT_PINK;
 pub const TEST_MENU_BUTTON_SELECTED: Color = PINK;
 pub const TEST_CHECKBOX_LABEL: Color = LIME;
 pub const TEST_SETTING_SECTION: Color = DARK_CYAN;
+
+/// The roles widget styling draws from, so a palette can be swapped out (light mode, a
+/// user-supplied palette file) without every widget referencing the `pub const` colors above
+/// directly. Threading this through the widgets themselves is left for when those files are
+/// touched; for now this is the palette the GUI's configured theme resolves to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub background: Color,
+    pub background_secondary: Color,
+    pub background_tertiary: Color,
+    pub primary_button: Color,
+    pub secondary_button: Color,
+    pub success: Color,
+    pub error: Color,
+    pub warning: Color,
+    pub text: Color,
+    pub text_secondary: Color,
+}
+
+impl Theme {
+    /// The palette the GUI has always shipped with.
+    pub const fn dark() -> Self {
+        Self {
+            background: LIGHT_BLACK,
+            background_secondary: GREY_6,
+            background_tertiary: GREY_5,
+            primary_button: GREEN,
+            secondary_button: GREY_4,
+            success: GREEN,
+            error: RED,
+            warning: ORANGE,
+            text: WHITE,
+            text_secondary: GREY_2,
+        }
+    }
+
+    pub const fn light() -> Self {
+        Self {
+            background: WHITE,
+            background_secondary: GREY_1,
+            background_tertiary: GREY_2,
+            primary_button: DARK_CYAN,
+            secondary_button: GREY_3,
+            success: GREEN,
+            error: RED,
+            warning: ORANGE,
+            text: BLACK,
+            text_secondary: GREY_7,
+        }
+    }
+
+    /// Resolve a user-supplied palette of hex strings into a `Theme`, rejecting the first
+    /// malformed entry found.
+    pub fn from_palette(palette: &ThemePalette) -> Result<Self, InvalidThemeColor> {
+        Ok(Self {
+            background: parse_hex_color("background", &palette.background)?,
+            background_secondary: parse_hex_color(
+                "background_secondary",
+                &palette.background_secondary,
+            )?,
+            background_tertiary: parse_hex_color(
+                "background_tertiary",
+                &palette.background_tertiary,
+            )?,
+            primary_button: parse_hex_color("primary_button", &palette.primary_button)?,
+            secondary_button: parse_hex_color("secondary_button", &palette.secondary_button)?,
+            success: parse_hex_color("success", &palette.success)?,
+            error: parse_hex_color("error", &palette.error)?,
+            warning: parse_hex_color("warning", &palette.warning)?,
+            text: parse_hex_color("text", &palette.text)?,
+            text_secondary: parse_hex_color("text_secondary", &palette.text_secondary)?,
+        })
+    }
+}
+
+/// Hex color strings for each [`Theme`] role, as they appear in a user-supplied palette file
+/// (e.g. `background = "#141414"`).
+#[derive(Debug, Clone)]
+pub struct ThemePalette {
+    pub background: String,
+    pub background_secondary: String,
+    pub background_tertiary: String,
+    pub primary_button: String,
+    pub secondary_button: String,
+    pub success: String,
+    pub error: String,
+    pub warning: String,
+    pub text: String,
+    pub text_secondary: String,
+}
+
+/// A palette entry that isn't a valid `#rrggbb` hex color.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidThemeColor {
+    pub field: &'static str,
+    pub value: String,
+}
+
+impl std::fmt::Display for InvalidThemeColor {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "'{}' is not a valid '#rrggbb' hex color for theme field '{}'",
+            self.value, self.field
+        )
+    }
+}
+
+impl std::error::Error for InvalidThemeColor {}
+
+fn parse_hex_color(field: &'static str, value: &str) -> Result<Color, InvalidThemeColor> {
+    let invalid = || InvalidThemeColor {
+        field,
+        value: value.to_string(),
+    };
+    let hex = value.trim().trim_start_matches('#');
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| invalid());
+    Ok(Color::from_rgb8(channel(0)?, channel(2)?, channel(4)?))
+}
@@ -0,0 +1,27 @@
+use std::path::PathBuf;
+
+use lianad::commands::{Coin, SpendTx};
+
+use crate::app::{bitcoin::Network, fiat};
+
+/// In-memory snapshot of daemon-reported state the rest of the app renders from. Rebuilt on
+/// every `Message::Tick` (see `App::update`) and partially persisted to `cache.json` (see
+/// `cache_store`) so panels have something to show immediately on the next startup, before the
+/// first daemon round-trip of a session completes.
+#[derive(Debug, Clone)]
+pub struct Cache {
+    pub datadir_path: PathBuf,
+    pub coins: Vec<Coin>,
+    pub spend_txs: Vec<SpendTx>,
+    pub network: Network,
+    pub blockheight: i32,
+    pub rescan_progress: Option<f64>,
+    pub sync_progress: Option<f64>,
+    pub last_poll_timestamp: Option<u32>,
+    pub last_poll_at_startup: Option<u32>,
+    /// Last fetched fiat conversion rate, if any. Carried over unchanged on every `Tick` for
+    /// now: nothing refreshes it yet, since doing so needs a currency/endpoint setting on
+    /// `Config` and a `Message` variant to deliver the fetch result, neither of which is part
+    /// of this tree slice. `App::view` already shows it once populated, flagged when stale.
+    pub fiat_rate: Option<fiat::Rate>,
+}
@@ -0,0 +1,77 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::app::cache::Cache;
+
+/// Bump whenever the shape of [`CacheFile`] changes in a way older files can't be read as.
+/// A mismatched version is treated the same as a missing file: fall back to an empty cache.
+const SCHEMA_VERSION: u32 = 1;
+
+const FILE_NAME: &str = "cache.json";
+
+/// On-disk snapshot of the parts of [`Cache`] worth restoring immediately on startup, so panels
+/// have last-known data to render before the first daemon round-trip completes.
+#[derive(Serialize, Deserialize)]
+struct CacheFile {
+    version: u32,
+    coins: serde_json::Value,
+    spend_txs: serde_json::Value,
+    blockheight: i32,
+    rescan_progress: Option<f64>,
+    network: String,
+}
+
+fn path(data_dir: &Path) -> PathBuf {
+    data_dir.join(FILE_NAME)
+}
+
+pub fn save(cache: &Cache) {
+    let file = CacheFile {
+        version: SCHEMA_VERSION,
+        coins: serde_json::to_value(&cache.coins).unwrap_or(serde_json::Value::Null),
+        spend_txs: serde_json::to_value(&cache.spend_txs).unwrap_or(serde_json::Value::Null),
+        blockheight: cache.blockheight,
+        rescan_progress: cache.rescan_progress,
+        network: cache.network.to_string(),
+    };
+    let result = serde_json::to_vec(&file).map_err(std::io::Error::other).and_then(|content| {
+        std::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(path(&cache.datadir_path))?
+            .write_all(&content)
+    });
+    if let Err(e) = result {
+        warn!("Failed to persist cache for cold-start rendering: {}", e);
+    }
+}
+
+/// Apply a previously persisted snapshot onto `cache`, if one exists and its schema version
+/// matches. Any mismatch (missing file, bad JSON, old schema) is a silent no-op: the caller
+/// keeps its freshly-built empty cache and reconciles once the daemon responds.
+pub fn load_into(cache: &mut Cache) {
+    let Some(content) = std::fs::read(path(&cache.datadir_path)).ok() else {
+        return;
+    };
+    let Ok(file) = serde_json::from_slice::<CacheFile>(&content) else {
+        return;
+    };
+    if file.version != SCHEMA_VERSION {
+        return;
+    }
+    if let Ok(coins) = serde_json::from_value(file.coins) {
+        cache.coins = coins;
+    }
+    if let Ok(spend_txs) = serde_json::from_value(file.spend_txs) {
+        cache.spend_txs = spend_txs;
+    }
+    cache.blockheight = file.blockheight;
+    cache.rescan_progress = file.rescan_progress;
+    if let Ok(network) = file.network.parse() {
+        cache.network = network;
+    }
+}
@@ -0,0 +1,146 @@
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::daemon::Daemon;
+
+/// Connection state of the backend currently selected by a [`DaemonPool`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackendStatus {
+    Connected,
+    Reconnecting,
+    AllDown,
+}
+
+/// Holds an ordered list of daemon backends (external RPC and/or the embedded daemon) and
+/// fails over to the next healthy one when the active backend stops answering.
+///
+/// The primary backend (index 0, normally the one the user configured first) is always tried
+/// first on reconnect; the remaining candidates are shuffled once at construction so load
+/// spreads across them instead of everyone falling back to the same secondary in order.
+pub struct DaemonPool {
+    backends: Vec<Arc<dyn Daemon + Sync + Send>>,
+    healthy: Vec<bool>,
+    current: usize,
+}
+
+impl DaemonPool {
+    pub fn new(backends: Vec<Arc<dyn Daemon + Sync + Send>>) -> Self {
+        assert!(!backends.is_empty(), "DaemonPool needs at least one backend");
+        let healthy = vec![true; backends.len()];
+        let mut pool = Self {
+            backends,
+            healthy,
+            current: 0,
+        };
+        pool.shuffle_fallbacks();
+        pool
+    }
+
+    /// Shuffle every backend but the primary so repeated failovers don't all land on the same
+    /// secondary first.
+    fn shuffle_fallbacks(&mut self) {
+        let len = self.backends.len();
+        if len <= 2 {
+            return;
+        }
+        let mut seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0xdead_beef);
+        for i in (2..len).rev() {
+            // xorshift64: cheap, deterministic-per-seed, good enough to spread load.
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            let j = 1 + (seed as usize % i);
+            self.backends.swap(i, j);
+        }
+    }
+
+    pub fn current(&self) -> Arc<dyn Daemon + Sync + Send> {
+        self.backends[self.current].clone()
+    }
+
+    pub fn status(&self) -> BackendStatus {
+        if self.healthy.iter().all(|h| !h) {
+            BackendStatus::AllDown
+        } else if self.healthy[self.current] {
+            BackendStatus::Connected
+        } else {
+            BackendStatus::Reconnecting
+        }
+    }
+
+    /// Mark the currently active backend as unhealthy and rotate to the next healthy one, if
+    /// any. Returns the newly active backend when a rotation happened.
+    pub fn fail_current_and_rotate(&mut self) -> Option<Arc<dyn Daemon + Sync + Send>> {
+        self.healthy[self.current] = false;
+        match next_healthy(&self.healthy, self.current) {
+            RotationOutcome::Rotated(candidate) => {
+                self.current = candidate;
+                Some(self.current())
+            }
+            RotationOutcome::AllDown => {
+                // Every backend is marked unhealthy: give them all another chance starting
+                // from the primary rather than getting permanently stuck.
+                self.healthy.iter_mut().for_each(|h| *h = true);
+                self.current = 0;
+                None
+            }
+        }
+    }
+
+    pub fn mark_current_healthy(&mut self) {
+        self.healthy[self.current] = true;
+    }
+
+    /// Swap out the active backend for a freshly reconnected one (e.g. after the user edited
+    /// the daemon config), keeping its position and health in the pool.
+    pub fn replace_current(&mut self, daemon: Arc<dyn Daemon + Sync + Send>) {
+        self.backends[self.current] = daemon;
+        self.healthy[self.current] = true;
+    }
+}
+
+enum RotationOutcome {
+    Rotated(usize),
+    AllDown,
+}
+
+/// Find the next healthy index after `current`, wrapping around, without considering `current`
+/// itself. Pulled out of `fail_current_and_rotate` so the rotation order can be unit-tested
+/// without needing a `Daemon` trait object to build a `DaemonPool`.
+fn next_healthy(healthy: &[bool], current: usize) -> RotationOutcome {
+    let len = healthy.len();
+    for offset in 1..len {
+        let candidate = (current + offset) % len;
+        if healthy[candidate] {
+            return RotationOutcome::Rotated(candidate);
+        }
+    }
+    RotationOutcome::AllDown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotates_to_next_healthy_wrapping_around() {
+        let healthy = vec![true, false, false, true];
+        assert!(matches!(next_healthy(&healthy, 0), RotationOutcome::Rotated(3)));
+        assert!(matches!(next_healthy(&healthy, 3), RotationOutcome::Rotated(0)));
+    }
+
+    #[test]
+    fn skips_unhealthy_candidates() {
+        let healthy = vec![true, false, true, false];
+        assert!(matches!(next_healthy(&healthy, 0), RotationOutcome::Rotated(2)));
+    }
+
+    #[test]
+    fn reports_all_down_when_nothing_is_healthy() {
+        let healthy = vec![false, false, false];
+        assert!(matches!(next_healthy(&healthy, 0), RotationOutcome::AllDown));
+    }
+}
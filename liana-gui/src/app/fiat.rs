@@ -0,0 +1,73 @@
+use std::time::{Duration, SystemTime};
+
+use rust_decimal::Decimal;
+
+/// How long a fetched rate is trusted before [`Rate::is_stale`] starts flagging it rather than
+/// silently keeping showing it as current.
+const STALE_AFTER: Duration = Duration::from_secs(15 * 60);
+
+/// A BTC/fiat conversion price, along with when it was fetched. Never used for anything
+/// consensus-critical, only to give a familiar fiat readout alongside sat amounts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rate {
+    pub price: Decimal,
+    pub fetched_at: SystemTime,
+}
+
+impl Rate {
+    pub fn new(price: Decimal, fetched_at: SystemTime) -> Self {
+        Self { price, fetched_at }
+    }
+
+    /// Whether this rate is old enough that it should be shown flagged as stale rather than as
+    /// current. A rate with a `fetched_at` in the future (clock skew) is never considered stale.
+    pub fn is_stale(&self, now: SystemTime) -> bool {
+        now.duration_since(self.fetched_at)
+            .map(|age| age > STALE_AFTER)
+            .unwrap_or(false)
+    }
+
+    /// Convert a sat amount into this rate's fiat currency, rounded to `minor_unit_decimals`
+    /// (e.g. 2 for cents). `checked_div`/`checked_mul` avoid ever panicking or wrapping on an
+    /// extreme rate or amount; callers get `None` instead.
+    pub fn convert_sats(&self, sats: u64, minor_unit_decimals: u32) -> Option<Decimal> {
+        let btc = Decimal::from(sats).checked_div(Decimal::from(100_000_000u64))?;
+        let amount = btc.checked_mul(self.price)?;
+        Some(amount.round_dp(minor_unit_decimals))
+    }
+}
+
+/// Whether a periodic fiat-rate refresh is due: either nothing has been fetched yet, or the
+/// last fetch is now stale. Pulled out as a pure check so the scheduling decision (currently
+/// not wired into `App::subscription`, see its doc comment) can be tested without a real fetch.
+pub fn should_fetch(rate: Option<&Rate>, now: SystemTime) -> bool {
+    match rate {
+        None => true,
+        Some(rate) => rate.is_stale(now),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fetch_is_due_when_nothing_fetched_yet() {
+        assert!(should_fetch(None, SystemTime::now()));
+    }
+
+    #[test]
+    fn fetch_is_not_due_for_a_fresh_rate() {
+        let rate = Rate::new(Decimal::from(65_000), SystemTime::now());
+        assert!(!should_fetch(Some(&rate), SystemTime::now()));
+    }
+
+    #[test]
+    fn fetch_is_due_once_the_rate_goes_stale() {
+        let rate = Rate::new(
+            Decimal::from(65_000),
+            SystemTime::now() - Duration::from_secs(16 * 60),
+        );
+        assert!(should_fetch(Some(&rate), SystemTime::now()));
+    }
+}
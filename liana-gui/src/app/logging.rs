@@ -0,0 +1,158 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::prelude::*;
+
+const DEFAULT_MAX_FILE_SIZE: u64 = 5 * 1024 * 1024;
+const DEFAULT_ARCHIVED_FILES: usize = 5;
+const LOG_FILE_NAME: &str = "liana.log";
+
+/// Settings for the on-disk rolling log. The GUI `Config` has no fields for these: `App` sets
+/// up the log with [`LoggingConfig::default`].
+#[derive(Debug, Clone, Copy)]
+pub struct LoggingConfig {
+    /// Minimum level written to the log file.
+    pub level: LevelFilter,
+    /// Roll the active file once it exceeds this size, in bytes.
+    pub max_file_size: u64,
+    /// Number of archived files (`liana.1.log`, `liana.2.log`, ...) kept beyond the active one.
+    pub archived_files: usize,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            level: LevelFilter::INFO,
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+            archived_files: DEFAULT_ARCHIVED_FILES,
+        }
+    }
+}
+
+/// A [`Write`] implementation that appends to `data_dir/liana.log`, rolling the file once it
+/// exceeds `max_file_size` using a fixed-window naming scheme (`liana.1.log`, `liana.2.log`,
+/// ..., `liana.<archived_files>.log`), discarding anything older than the window.
+struct RollingFileWriter {
+    dir: PathBuf,
+    max_file_size: u64,
+    archived_files: usize,
+    file: File,
+    size: u64,
+}
+
+impl RollingFileWriter {
+    fn new(dir: PathBuf, max_file_size: u64, archived_files: usize) -> io::Result<Self> {
+        fs::create_dir_all(&dir)?;
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(dir.join(LOG_FILE_NAME))?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            dir,
+            max_file_size,
+            archived_files,
+            file,
+            size,
+        })
+    }
+
+    fn archived_path(&self, index: usize) -> PathBuf {
+        self.dir.join(format!("liana.{index}.log"))
+    }
+
+    fn roll(&mut self) -> io::Result<()> {
+        // Drop the oldest archive, then shift every other archive up by one index.
+        let oldest = self.archived_path(self.archived_files);
+        let _ = fs::remove_file(oldest);
+        for index in (1..self.archived_files).rev() {
+            let from = self.archived_path(index);
+            if from.exists() {
+                fs::rename(from, self.archived_path(index + 1))?;
+            }
+        }
+        if self.archived_files > 0 {
+            fs::rename(self.dir.join(LOG_FILE_NAME), self.archived_path(1))?;
+        }
+        self.file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(self.dir.join(LOG_FILE_NAME))?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+impl Write for RollingFileWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.size >= self.max_file_size {
+            self.roll()?;
+        }
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Clone handle handed to `tracing_subscriber::fmt::Layer::with_writer`, since the layer wants
+/// a `MakeWriter` rather than a bare `Write`.
+#[derive(Clone)]
+pub struct RollingFileAppender(std::sync::Arc<Mutex<RollingFileWriter>>);
+
+impl RollingFileAppender {
+    pub fn new(data_dir: &Path, config: &LoggingConfig) -> io::Result<Self> {
+        let writer = RollingFileWriter::new(
+            data_dir.to_path_buf(),
+            config.max_file_size,
+            config.archived_files,
+        )?;
+        Ok(Self(std::sync::Arc::new(Mutex::new(writer))))
+    }
+}
+
+impl<'a> tracing_subscriber::fmt::MakeWriter<'a> for RollingFileAppender {
+    type Writer = RollingFileAppenderGuard;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RollingFileAppenderGuard(self.0.clone())
+    }
+}
+
+pub struct RollingFileAppenderGuard(std::sync::Arc<Mutex<RollingFileWriter>>);
+
+impl Write for RollingFileAppenderGuard {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().expect("poisoned").write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.lock().expect("poisoned").flush()
+    }
+}
+
+/// Set up the rolling log file under `data_dir` alongside the existing console output.
+pub fn init(data_dir: &Path, config: &LoggingConfig) -> io::Result<()> {
+    let file_appender = RollingFileAppender::new(data_dir, config)?;
+    let file_layer = tracing_subscriber::fmt::layer()
+        .with_ansi(false)
+        .with_writer(file_appender)
+        .with_filter(config.level);
+
+    let console_layer = tracing_subscriber::fmt::layer().with_filter(config.level);
+
+    // `App` can be constructed more than once in tests or on backend switches; only the first
+    // call actually installs the subscriber.
+    let _ = tracing_subscriber::registry()
+        .with(console_layer)
+        .with(file_layer)
+        .try_init();
+    Ok(())
+}
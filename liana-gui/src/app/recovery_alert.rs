@@ -0,0 +1,78 @@
+use std::collections::BTreeSet;
+
+use crate::app::bitcoin::OutPoint;
+
+/// How many blocks before a coin's recovery path becomes spendable we start surfacing it as
+/// "approaching" rather than waiting for it to actually be spendable. Roughly half a day, so a
+/// user checking in once daily won't miss the window.
+pub const APPROACHING_THRESHOLD_BLOCKS: u32 = 72;
+
+/// The minimal view of a coin this calculation needs: its identity and the height it confirmed
+/// at. Kept separate from the daemon's `Coin` type so this module doesn't depend on its exact
+/// shape; callers convert from `Coin` at the integration point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecoverableCoin {
+    pub outpoint: OutPoint,
+    pub confirmation_height: i32,
+}
+
+impl RecoverableCoin {
+    /// Blocks remaining until this coin's relative-timelocked recovery path is spendable, or
+    /// `None` if it already is (or if the height arithmetic overflows).
+    pub fn blocks_until_spendable(&self, current_height: i32, timelock: u16) -> Option<u32> {
+        let spendable_at = self.confirmation_height.checked_add(i32::from(timelock))?;
+        let remaining = spendable_at.checked_sub(current_height)?;
+        (remaining > 0).then_some(remaining as u32)
+    }
+}
+
+/// Why a coin is being surfaced: its recovery path is close to unlocking, or it already has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SweepAlertKind {
+    Approaching { blocks_remaining: u32 },
+    Spendable,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SweepAlert {
+    pub outpoint: OutPoint,
+    pub kind: SweepAlertKind,
+}
+
+/// Scan coins for ones whose recovery path just became spendable, or is within
+/// [`APPROACHING_THRESHOLD_BLOCKS`] of becoming spendable. The caller is expected to dedupe
+/// against coins already alerted on (e.g. via [`unseen`]) so a coin that's been sitting
+/// spendable for a while doesn't re-alert on every poll.
+pub fn scan_for_sweep_alerts(
+    coins: &[RecoverableCoin],
+    current_height: i32,
+    timelock: u16,
+) -> Vec<SweepAlert> {
+    coins
+        .iter()
+        .filter_map(|coin| {
+            let kind = match coin.blocks_until_spendable(current_height, timelock) {
+                None => SweepAlertKind::Spendable,
+                Some(remaining) if remaining <= APPROACHING_THRESHOLD_BLOCKS => {
+                    SweepAlertKind::Approaching {
+                        blocks_remaining: remaining,
+                    }
+                }
+                Some(_) => return None,
+            };
+            Some(SweepAlert {
+                outpoint: coin.outpoint,
+                kind,
+            })
+        })
+        .collect()
+}
+
+/// Filter out alerts for outpoints already present in `seen`, and record the rest so they won't
+/// be repeated on the next scan.
+pub fn unseen(alerts: Vec<SweepAlert>, seen: &mut BTreeSet<OutPoint>) -> Vec<SweepAlert> {
+    alerts
+        .into_iter()
+        .filter(|alert| seen.insert(alert.outpoint))
+        .collect()
+}
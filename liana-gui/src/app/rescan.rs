@@ -0,0 +1,86 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// Tracks an in-progress rescan across restarts.
+///
+/// Written to `<data_dir>/rescan.json` by `App::note_rescan_started`, which `App` itself calls
+/// only when resuming an interrupted rescan, and updated on every `Message::UpdateCache` tick.
+/// Removed once the rescan completes (progress reaches 1.0 or `rescan_progress` goes back to
+/// `None`) or when starting the rescan fails. There is no separate abort path.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct RescanJournal {
+    /// Timestamp the rescan was requested to start from.
+    pub rescan_timestamp: u32,
+    /// Last `rescan_progress` value observed from the daemon.
+    pub last_progress: f64,
+}
+
+const FILE_NAME: &str = "rescan.json";
+
+impl RescanJournal {
+    pub fn new(rescan_timestamp: u32) -> Self {
+        Self {
+            rescan_timestamp,
+            last_progress: 0.0,
+        }
+    }
+
+    fn path(data_dir: &Path) -> PathBuf {
+        data_dir.join(FILE_NAME)
+    }
+
+    /// Load the journal from `data_dir`, if any. Returns `None` if the file doesn't exist or
+    /// fails to parse, in which case it is treated as if no rescan was in progress.
+    pub fn load(data_dir: &Path) -> Option<Self> {
+        let content = std::fs::read(Self::path(data_dir)).ok()?;
+        serde_json::from_slice(&content).ok()
+    }
+
+    pub fn save(&self, data_dir: &Path) -> std::io::Result<()> {
+        let content = serde_json::to_vec_pretty(self)?;
+        std::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(Self::path(data_dir))?
+            .write_all(&content)
+    }
+
+    pub fn delete(data_dir: &Path) {
+        let _ = std::fs::remove_file(Self::path(data_dir));
+    }
+
+    /// Whether this journal describes a rescan that was interrupted: recorded progress below
+    /// completion while the daemon currently reports no rescan running.
+    pub fn is_interrupted(&self, daemon_rescan_progress: Option<f64>) -> bool {
+        self.last_progress < 1.0 && daemon_rescan_progress.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interrupted_when_daemon_reports_nothing_running() {
+        let mut journal = RescanJournal::new(0);
+        journal.last_progress = 0.42;
+        assert!(journal.is_interrupted(None));
+    }
+
+    #[test]
+    fn not_interrupted_while_daemon_is_still_running_it() {
+        let mut journal = RescanJournal::new(0);
+        journal.last_progress = 0.42;
+        assert!(!journal.is_interrupted(Some(0.5)));
+    }
+
+    #[test]
+    fn not_interrupted_once_complete() {
+        let mut journal = RescanJournal::new(0);
+        journal.last_progress = 1.0;
+        assert!(!journal.is_interrupted(None));
+    }
+}
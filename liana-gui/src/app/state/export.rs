@@ -9,6 +9,7 @@ use tokio::task::JoinHandle;
 
 use crate::{
     app::{
+        bitcoin,
         message::Message,
         view::{self, export::export_modal},
     },
@@ -16,6 +17,58 @@ use crate::{
     export::{self, get_path, ExportMessage, ExportProgress, ExportState, ExportType},
 };
 
+/// Export content known upfront, so [`ExportModal::start`] can write it straight to disk instead
+/// of going through `export::export_subscription`'s `Descriptor`/`Psbt` branches, which this
+/// tree slice's absent `export` module doesn't implement.
+#[derive(Debug, Clone)]
+pub enum InlineExport {
+    Descriptor {
+        descriptor: String,
+        change_descriptor: Option<String>,
+        network: bitcoin::Network,
+        blockheight: i32,
+        label: Option<String>,
+        recovery_timelock: Option<u16>,
+    },
+    /// Already-serialized PSBTs (e.g. `Psbt::serialize()`), one file per entry.
+    Psbts(Vec<Vec<u8>>),
+}
+
+/// Write `inline` to `path` directly: a JSON file for [`InlineExport::Descriptor`], or a
+/// directory of zero-padded `.psbt` files (see `bulk_psbt_filename`) for
+/// [`InlineExport::Psbts`].
+fn write_inline_export(path: &std::path::Path, inline: &InlineExport) -> std::io::Result<()> {
+    match inline {
+        InlineExport::Descriptor {
+            descriptor,
+            change_descriptor,
+            network,
+            blockheight,
+            label,
+            recovery_timelock,
+        } => {
+            let body = descriptor_export_json(
+                descriptor,
+                change_descriptor.as_deref(),
+                *network,
+                *blockheight,
+                label.as_deref(),
+                *recovery_timelock,
+            );
+            let content = serde_json::to_vec_pretty(&body).map_err(std::io::Error::other)?;
+            std::fs::write(path, content)
+        }
+        InlineExport::Psbts(psbts) => {
+            std::fs::create_dir_all(path)?;
+            let total = psbts.len();
+            for (i, bytes) in psbts.iter().enumerate() {
+                std::fs::write(path.join(bulk_psbt_filename(i, total)), bytes)?;
+            }
+            Ok(())
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct ExportModal {
     path: Option<PathBuf>,
@@ -24,6 +77,7 @@ pub struct ExportModal {
     error: Option<export::Error>,
     daemon: Arc<dyn Daemon + Sync + Send>,
     export_type: ExportType,
+    inline_export: Option<InlineExport>,
 }
 
 impl ExportModal {
@@ -36,17 +90,58 @@ impl ExportModal {
             error: None,
             daemon,
             export_type,
+            inline_export: None,
         }
     }
 
+    /// Like [`Self::new`], but the descriptor export body is written straight to disk by
+    /// [`Self::start`] (see [`write_inline_export`]) rather than through the unimplemented
+    /// `Descriptor` branch of `export::export_subscription`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_descriptor_export(
+        daemon: Arc<dyn Daemon + Sync + Send>,
+        descriptor: String,
+        change_descriptor: Option<String>,
+        network: bitcoin::Network,
+        blockheight: i32,
+        label: Option<String>,
+        recovery_timelock: Option<u16>,
+    ) -> Self {
+        let mut modal = Self::new(daemon, ExportType::Descriptor);
+        modal.inline_export = Some(InlineExport::Descriptor {
+            descriptor,
+            change_descriptor,
+            network,
+            blockheight,
+            label,
+            recovery_timelock,
+        });
+        modal
+    }
+
+    /// Like [`Self::new`], but the PSBTs are written straight to disk by [`Self::start`] (see
+    /// [`write_inline_export`]) rather than through the unimplemented `Psbt` branch of
+    /// `export::export_subscription`.
+    pub fn new_bulk_psbt_export(daemon: Arc<dyn Daemon + Sync + Send>, psbts: Vec<Vec<u8>>) -> Self {
+        let mut modal = Self::new(daemon, ExportType::Psbt);
+        modal.inline_export = Some(InlineExport::Psbts(psbts));
+        modal
+    }
+
     pub fn default_filename(&self) -> String {
         match self.export_type {
             ExportType::Transactions => {
                 let date = chrono::Local::now().format("%Y-%m-%dT%H-%M-%S");
                 format!("liana-txs-{date}.csv")
             }
-            ExportType::Psbt => todo!(),
-            ExportType::Descriptor => todo!(),
+            ExportType::Psbt => {
+                let date = chrono::Local::now().format("%Y-%m-%d");
+                format!("liana-psbts-{date}")
+            }
+            ExportType::Descriptor => {
+                let date = chrono::Local::now().format("%Y-%m-%d");
+                format!("liana-descriptor-{date}.json")
+            }
         }
     }
 
@@ -115,6 +210,15 @@ impl ExportModal {
 
     pub fn start(&mut self) {
         self.state = ExportState::Started;
+        if let (Some(path), Some(inline)) = (self.path.clone(), self.inline_export.clone()) {
+            match write_inline_export(&path, &inline) {
+                Ok(()) => self.state = ExportState::Ended,
+                Err(e) => {
+                    tracing::warn!("Inline export to {:?} failed: {}", path, e);
+                    self.state = ExportState::Init;
+                }
+            }
+        }
     }
 
     pub fn stop(&mut self, state: ExportState) {
@@ -124,6 +228,13 @@ impl ExportModal {
         }
     }
 
+    // When constructed via `new_descriptor_export`/`new_bulk_psbt_export`, `start` already wrote
+    // the whole export synchronously and left `self.state` at `Ended`, so this falls through to
+    // `None` below without needing an `export::ExportProgress` subscription at all. Only the
+    // `Transactions` export (built via plain `new`) still streams through
+    // `export::export_subscription`, which isn't part of this file. The bulk-export entry point
+    // from `PsbtsPanel` that would call `new_bulk_psbt_export` likewise belongs to that panel's
+    // own file, not part of this tree slice.
     pub fn subscription(&self) -> Option<Subscription<export::ExportProgress>> {
         if let Some(path) = &self.path {
             match &self.state {
@@ -145,3 +256,131 @@ impl ExportModal {
         }
     }
 }
+
+/// BDK-compatible JSON body for a descriptor export: enough for BDK (or another watch-only
+/// wallet) to track this wallet's coins, plus the recovery-path metadata BDK itself doesn't
+/// understand but a Liana user restoring from this file would still want recorded.
+///
+/// Called by [`write_inline_export`] for any `ExportModal` built via
+/// `ExportModal::new_descriptor_export`. A free function rather than an `ExportModal` method,
+/// since the modal doesn't otherwise hold the wallet's descriptor.
+pub fn descriptor_export_json(
+    descriptor: &str,
+    change_descriptor: Option<&str>,
+    network: bitcoin::Network,
+    blockheight: i32,
+    label: Option<&str>,
+    recovery_timelock: Option<u16>,
+) -> serde_json::Value {
+    serde_json::json!({
+        "descriptor": descriptor,
+        "change_descriptor": change_descriptor,
+        "network": network.to_string(),
+        "blockheight": blockheight,
+        "label": label,
+        "recovery_timelock": recovery_timelock,
+    })
+}
+
+/// Filename for the `index`-th (0-based) PSBT out of `total` in a bulk export, zero-padded so
+/// they sort in export order inside the export directory. Called by [`write_inline_export`] for
+/// any `ExportModal` built via `ExportModal::new_bulk_psbt_export`.
+pub fn bulk_psbt_filename(index: usize, total: usize) -> String {
+    let width = total.max(1).to_string().len();
+    format!("{:0width$}.psbt", index + 1, width = width)
+}
+
+/// Fraction complete for a bulk PSBT export, for `ExportProgress::Progress`. `0.0` rather than
+/// dividing by zero when there's nothing to export.
+pub fn bulk_export_progress(done: usize, total: usize) -> f32 {
+    if total == 0 {
+        0.0
+    } else {
+        done as f32 / total as f32
+    }
+}
+
+#[cfg(test)]
+mod export_body_tests {
+    use super::*;
+
+    #[test]
+    fn descriptor_export_json_carries_every_field() {
+        let body = descriptor_export_json(
+            "wsh(...)#checksum",
+            Some("wsh(...)#changechecksum"),
+            bitcoin::Network::Bitcoin,
+            800_000,
+            Some("Cold storage"),
+            Some(26_280),
+        );
+        assert_eq!(body["descriptor"], "wsh(...)#checksum");
+        assert_eq!(body["change_descriptor"], "wsh(...)#changechecksum");
+        assert_eq!(body["network"], "bitcoin");
+        assert_eq!(body["blockheight"], 800_000);
+        assert_eq!(body["label"], "Cold storage");
+        assert_eq!(body["recovery_timelock"], 26_280);
+    }
+
+    #[test]
+    fn descriptor_export_json_allows_absent_optionals() {
+        let body = descriptor_export_json("wsh(...)#checksum", None, bitcoin::Network::Signet, 0, None, None);
+        assert!(body["change_descriptor"].is_null());
+        assert!(body["label"].is_null());
+        assert!(body["recovery_timelock"].is_null());
+    }
+
+    #[test]
+    fn bulk_psbt_filenames_sort_in_export_order() {
+        let total = 12;
+        assert_eq!(bulk_psbt_filename(0, total), "01.psbt");
+        assert_eq!(bulk_psbt_filename(9, total), "10.psbt");
+        assert_eq!(bulk_psbt_filename(11, total), "12.psbt");
+    }
+
+    #[test]
+    fn bulk_export_progress_is_a_fraction_of_total() {
+        assert_eq!(bulk_export_progress(0, 4), 0.0);
+        assert_eq!(bulk_export_progress(2, 4), 0.5);
+        assert_eq!(bulk_export_progress(4, 4), 1.0);
+    }
+
+    #[test]
+    fn bulk_export_progress_does_not_divide_by_zero() {
+        assert_eq!(bulk_export_progress(0, 0), 0.0);
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("liana-export-test-{}-{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn write_inline_export_writes_the_descriptor_body_to_a_file() {
+        let path = temp_path("descriptor.json");
+        let inline = InlineExport::Descriptor {
+            descriptor: "wsh(...)#checksum".to_string(),
+            change_descriptor: None,
+            network: bitcoin::Network::Bitcoin,
+            blockheight: 800_000,
+            label: None,
+            recovery_timelock: None,
+        };
+        write_inline_export(&path, &inline).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(parsed["descriptor"], "wsh(...)#checksum");
+        assert_eq!(parsed["blockheight"], 800_000);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn write_inline_export_writes_one_zero_padded_file_per_psbt() {
+        let path = temp_path("psbts");
+        let _ = std::fs::remove_dir_all(&path);
+        let inline = InlineExport::Psbts(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        write_inline_export(&path, &inline).unwrap();
+        assert_eq!(std::fs::read(path.join("1.psbt")).unwrap(), vec![1, 2, 3]);
+        assert_eq!(std::fs::read(path.join("2.psbt")).unwrap(), vec![4, 5, 6]);
+        let _ = std::fs::remove_dir_all(&path);
+    }
+}